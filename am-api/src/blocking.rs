@@ -0,0 +1,98 @@
+//! Blocking api client for synchronous callers
+//!
+//! The blocking client wraps an async [`crate::ApiClient`] together with its own runtime.
+//! Every request builder works with it unchanged, futures are driven with [`ApiClient::block_on`]
+//! and streams are turned into iterators with [`ApiClient::iter`]
+//!
+//! # Examples
+//!
+//! ```no_run,ignore
+//! let client = blocking::ApiClient::new(async_client)?;
+//!
+//! let album = client.block_on(Album::get().one(&client, "1"))?;
+//! for song in client.iter(LibrarySong::get().all(&client)) {
+//!     println!("{:?}", song?);
+//! }
+//! ```
+//!
+//! The blocking client must not be used from within an async runtime
+
+use crate::error::Error;
+use futures::{Stream, StreamExt};
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use tokio::runtime::Runtime;
+
+/// Blocking apple music api client
+///
+/// Dereferences to the wrapped [`crate::ApiClient`] so it can be passed to every request builder
+pub struct ApiClient {
+    client: crate::ApiClient,
+    runtime: Runtime,
+}
+
+impl ApiClient {
+    /// Create a new [`ApiClient`] instance wrapping an async client
+    pub fn new(client: crate::ApiClient) -> Result<ApiClient, Error> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(ApiClient { client, runtime })
+    }
+
+    /// Run a future to completion, blocking the current thread
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Turn a stream into an iterator, blocking the current thread for every item
+    pub fn iter<S: Stream>(&self, stream: S) -> Iter<'_, S> {
+        Iter {
+            runtime: &self.runtime,
+            stream: Box::pin(stream),
+        }
+    }
+
+    /// Get the wrapped async client
+    pub fn into_inner(self) -> crate::ApiClient {
+        self.client
+    }
+}
+
+impl Deref for ApiClient {
+    type Target = crate::ApiClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl DerefMut for ApiClient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+/// Blocking iterator over a stream, see [`ApiClient::iter`]
+pub struct Iter<'a, S> {
+    runtime: &'a Runtime,
+    stream: Pin<Box<S>>,
+}
+
+impl<'a, S: Stream> Iterator for Iter<'a, S> {
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
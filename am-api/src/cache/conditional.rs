@@ -0,0 +1,74 @@
+//! Conditional request revalidation
+
+use crate::cache::{CacheKey, DEFAULT_CAPACITY};
+use crate::transport::TransportResponse;
+use lru::LruCache;
+use reqwest::header;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+/// Store of responses carrying `ETag` or `Last-Modified` validators, shared by all clones of an [`ApiClient`](crate::ApiClient)
+///
+/// Requests for a stored response are sent with `If-None-Match` and `If-Modified-Since`,
+/// a `304 Not Modified` answer is replaced by the stored response with [`TransportResponse::not_modified`] set.
+/// Unlike [`ResponseCache`](crate::cache::ResponseCache) every request still reaches the api,
+/// which makes it suitable for polling personalized routes, their responses are kept apart per media user token
+#[derive(Debug)]
+pub struct ConditionalCache {
+    entries: Mutex<LruCache<CacheKey, TransportResponse>>,
+}
+
+impl ConditionalCache {
+    /// Create a new [`ConditionalCache`] instance holding at most `capacity` responses
+    pub fn new(capacity: usize) -> ConditionalCache {
+        ConditionalCache {
+            entries: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN),
+            )),
+        }
+    }
+
+    /// Get the amount of stored responses
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove all stored responses
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Get a stored response
+    pub fn get(&self, key: &CacheKey) -> Option<TransportResponse> {
+        self.lock().get(key).cloned()
+    }
+
+    /// Store a response, unsuccessful responses and responses without validators are ignored
+    pub fn insert(&self, key: CacheKey, response: &TransportResponse) {
+        let has_validators = response.headers.contains_key(header::ETAG)
+            || response.headers.contains_key(header::LAST_MODIFIED);
+
+        if !has_validators || !response.status.is_success() {
+            return;
+        }
+
+        let mut response = response.clone();
+        response.not_modified = false;
+        self.lock().put(key, response);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<CacheKey, TransportResponse>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ConditionalCache {
+    fn default() -> Self {
+        ConditionalCache::new(DEFAULT_CAPACITY)
+    }
+}
@@ -0,0 +1,135 @@
+//! On-disk response caching
+
+use crate::cache::CacheKey;
+use crate::error::Error;
+use crate::rate_limit::RouteKind;
+use crate::transport::TransportResponse;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// File-backed cache of successful catalog `GET` responses
+///
+/// Every response is stored in its own file named after a hash of its [`CacheKey`],
+/// so a cache directory can be reused across runs. Personalized routes under `/v1/me` are never stored.
+///
+/// Raw responses are stored and parsed again on every load, attaching the context of the loading request,
+/// so relationships and views of cached resources can still be iterated
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    directory: PathBuf,
+    ttl: Option<Duration>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskCacheEntry {
+    path: String,
+    stored_at: u64,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl DiskCache {
+    /// Create a new [`DiskCache`] instance storing responses in `directory`
+    ///
+    /// The directory is created when the first response is stored
+    pub fn new(directory: impl Into<PathBuf>) -> DiskCache {
+        DiskCache {
+            directory: directory.into(),
+            ttl: None,
+        }
+    }
+
+    /// Set the time to live for stored responses, by default responses never expire
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Get the directory responses are stored in
+    pub fn get_directory(&self) -> &Path {
+        self.directory.as_path()
+    }
+
+    /// Get a stored response, unreadable or expired entries are treated as missing
+    pub async fn get(&self, key: &CacheKey) -> Option<TransportResponse> {
+        let contents = tokio::fs::read(self.entry_path(key)).await.ok()?;
+        let entry = serde_json::from_slice::<DiskCacheEntry>(&contents).ok()?;
+
+        if let Some(ttl) = self.ttl {
+            if unix_now().saturating_sub(entry.stored_at) >= ttl.as_secs() {
+                return None;
+            }
+        }
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in entry.headers {
+            headers.append(
+                HeaderName::try_from(name).ok()?,
+                HeaderValue::try_from(value).ok()?,
+            );
+        }
+
+        Some(TransportResponse {
+            status: StatusCode::from_u16(entry.status).ok()?,
+            headers,
+            body: STANDARD.decode(entry.body).ok()?,
+            not_modified: false,
+        })
+    }
+
+    /// Store a response, unsuccessful responses and personalized routes are ignored
+    pub async fn insert(&self, key: &CacheKey, response: &TransportResponse) -> Result<(), Error> {
+        if RouteKind::from_path(&key.path) != RouteKind::Catalog || !response.status.is_success() {
+            return Ok(());
+        }
+
+        let entry = DiskCacheEntry {
+            path: key.path.clone(),
+            stored_at: unix_now(),
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: STANDARD.encode(&response.body),
+        };
+
+        tokio::fs::create_dir_all(&self.directory).await?;
+
+        // write to a temporary file first so concurrent readers never see a partial entry
+        let path = self.entry_path(key);
+        let temporary_path = path.with_extension(format!("{}.tmp", fastrand::u64(..)));
+        tokio::fs::write(&temporary_path, serde_json::to_vec(&entry)?).await?;
+        tokio::fs::rename(&temporary_path, &path).await?;
+
+        Ok(())
+    }
+
+    /// Remove all stored responses
+    pub async fn clear(&self) -> Result<(), Error> {
+        match tokio::fs::remove_dir_all(&self.directory).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.directory.join(format!("{}.json", key.digest()))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
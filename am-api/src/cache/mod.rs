@@ -0,0 +1,203 @@
+//! Response caching
+
+use crate::transport::{TransportRequest, TransportResponse};
+use lru::LruCache;
+use reqwest::Method;
+use sha2::{Digest, Sha256};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+pub mod conditional;
+pub mod disk;
+
+/// Default maximum amount of cached responses
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Default time to live for cached responses
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 10);
+
+/// In-memory cache of successful `GET` responses, shared by all clones of an [`ApiClient`](crate::ApiClient)
+///
+/// Responses are keyed on the method, endpoint and normalized query of the request, and the media user token for personalized routes.
+/// Personalized routes under `/v1/me` are not cached unless a ttl is set for them with [`ResponseCache::route_ttl`]
+#[derive(Debug)]
+pub struct ResponseCache {
+    default_ttl: Duration,
+    route_ttls: Vec<(Vec<String>, Duration)>,
+    entries: Mutex<LruCache<CacheKey, CacheEntry>>,
+}
+
+/// Cache key of a request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Request method
+    pub method: Method,
+    /// Endpoint path
+    pub path: String,
+    /// Sorted query parameters
+    pub query: Vec<(String, String)>,
+    /// Digest of the media user token of a personalized request, keeping responses of different users apart
+    pub user: Option<String>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    response: TransportResponse,
+    expires_at: Instant,
+}
+
+impl ResponseCache {
+    /// Create a new [`ResponseCache`] instance holding at most `capacity` responses
+    pub fn new(capacity: usize) -> ResponseCache {
+        ResponseCache {
+            default_ttl: DEFAULT_TTL,
+            route_ttls: Vec::new(),
+            entries: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN),
+            )),
+        }
+        .route_ttl("/v1/me", Duration::ZERO)
+    }
+
+    /// Set the time to live for routes without a more specific ttl
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Set the time to live for routes starting with `route`, a zero ttl disables caching for them
+    ///
+    /// `*` matches any single path segment, for example `/v1/catalog/*/charts`.
+    /// When multiple routes match a request, the longest one is used
+    pub fn route_ttl(mut self, route: &str, ttl: Duration) -> Self {
+        let segments = split_path(route).map(str::to_string).collect::<Vec<_>>();
+
+        self.route_ttls.retain(|(e, _)| *e != segments);
+        self.route_ttls.push((segments, ttl));
+        self
+    }
+
+    /// Get the time to live for an endpoint path
+    pub fn get_ttl(&self, path: &str) -> Duration {
+        let segments = split_path(path).collect::<Vec<_>>();
+
+        self.route_ttls
+            .iter()
+            .filter(|(route, _)| {
+                route.len() <= segments.len()
+                    && route
+                        .iter()
+                        .zip(&segments)
+                        .all(|(route, segment)| route == "*" || route == segment)
+            })
+            .max_by_key(|(route, _)| route.len())
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// Get the amount of cached responses, including expired ones that were not evicted yet
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove all cached responses
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Get a cached response
+    pub fn get(&self, key: &CacheKey) -> Option<TransportResponse> {
+        let mut entries = self.lock();
+
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.response.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache a response, unsuccessful responses and routes with a zero ttl are ignored
+    pub fn insert(&self, key: CacheKey, response: &TransportResponse) {
+        let ttl = self.get_ttl(&key.path);
+        if ttl.is_zero() || !response.status.is_success() {
+            return;
+        }
+
+        let mut response = response.clone();
+        response.not_modified = false;
+
+        self.lock().put(
+            key,
+            CacheEntry {
+                response,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<CacheKey, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        ResponseCache::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl CacheKey {
+    /// Create a cache key for a request, `None` if the request is not cacheable
+    pub fn new(request: &TransportRequest, base_url: &str) -> Option<CacheKey> {
+        if request.method != Method::GET {
+            return None;
+        }
+
+        Some(CacheKey {
+            method: request.method.clone(),
+            path: request.path(base_url).to_string(),
+            query: request.normalized_query(),
+            user: None,
+        })
+    }
+
+    /// Scope this key to the user of a media user token
+    pub fn with_user(mut self, media_user_token: &str) -> Self {
+        self.user = Some(format!("{:x}", Sha256::digest(media_user_token)));
+        self
+    }
+
+    /// Get a stable hex digest of this key
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.method.as_str());
+        hasher.update(" ");
+        hasher.update(&self.path);
+        for (key, value) in &self.query {
+            hasher.update("\n");
+            hasher.update(key);
+            hasher.update("=");
+            hasher.update(value);
+        }
+        if let Some(user) = &self.user {
+            hasher.update("\nuser=");
+            hasher.update(user);
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|e| !e.is_empty())
+}
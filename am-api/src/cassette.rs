@@ -0,0 +1,306 @@
+//! Record/replay cassettes
+
+use crate::error::Error;
+use crate::transport::{Transport, TransportRequest, TransportResponse};
+use futures::future::BoxFuture;
+use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Placeholder replacing credentials in recordings
+const REDACTED: &str = "[redacted]";
+
+/// Response headers never written to a recording
+const SCRUBBED_HEADERS: [&str; 1] = ["set-cookie"];
+
+/// Cassette mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CassetteMode {
+    /// Send requests and write every request and response to the fixture file
+    Record,
+    /// Serve responses from the fixture file without sending requests
+    Replay,
+}
+
+/// Fixture file of recorded requests and responses
+///
+/// Requests are matched by method, path, normalized query and body. In replay mode
+/// identical requests are served in the order they were recorded, the last match is
+/// repeated once all of them were served, and unmatched requests fail with [`Error::CassetteMiss`].
+///
+/// Authorization and media user tokens are replaced with a placeholder before anything is written,
+/// so recordings can be committed
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// let cassette = match std::env::var("AM_API_RECORD") {
+///     Ok(_) => Cassette::record("tests/fixtures/album.json"),
+///     Err(_) => Cassette::replay("tests/fixtures/album.json")?,
+/// };
+/// client.set_cassette(cassette);
+/// ```
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<CassetteState>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    replayed: Vec<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+impl Cassette {
+    /// Create a new [`Cassette`] instance recording to `path`, replacing any previous recording
+    pub fn record(path: impl Into<PathBuf>) -> Cassette {
+        Cassette {
+            path: path.into(),
+            mode: CassetteMode::Record,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Create a new [`Cassette`] instance replaying the recording at `path`
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Cassette, Error> {
+        let path = path.into();
+        let file = serde_json::from_slice::<CassetteFile>(&std::fs::read(&path)?)?;
+
+        Ok(Cassette {
+            path,
+            mode: CassetteMode::Replay,
+            state: Mutex::new(CassetteState {
+                replayed: vec![false; file.interactions.len()],
+                interactions: file.interactions,
+            }),
+        })
+    }
+
+    /// Get the fixture file path
+    pub fn get_path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Get the cassette mode
+    pub fn get_mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Wrap a transport with this cassette
+    pub(crate) fn wrap(self, transport: Arc<dyn Transport>) -> CassetteTransport {
+        CassetteTransport {
+            cassette: self,
+            transport,
+        }
+    }
+
+    async fn replay_interaction(
+        &self,
+        request: &TransportRequest,
+    ) -> Result<TransportResponse, Error> {
+        let recorded = RecordedRequest::new(request, &[]);
+        let mut state = self.state.lock().await;
+        let CassetteState {
+            interactions,
+            replayed,
+        } = &mut *state;
+
+        let matches = interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.request == recorded)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        let index = matches
+            .iter()
+            .find(|i| !replayed[**i])
+            .or(matches.last())
+            .copied()
+            .ok_or_else(|| Error::CassetteMiss(recorded.to_string()))?;
+
+        replayed[index] = true;
+        interactions[index].response.to_response()
+    }
+
+    async fn record_interaction(
+        &self,
+        request: &TransportRequest,
+        response: &TransportResponse,
+    ) -> Result<(), Error> {
+        let secrets = secrets(&request.headers);
+        let interaction = Interaction {
+            request: RecordedRequest::new(request, &secrets),
+            response: RecordedResponse::new(response, &secrets),
+        };
+
+        let mut state = self.state.lock().await;
+        state.interactions.push(interaction);
+
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&self.path, serde_json::to_vec_pretty(&file)?).await?;
+
+        Ok(())
+    }
+}
+
+/// [`Transport`] serving requests from a [`Cassette`] or recording them to it
+pub(crate) struct CassetteTransport {
+    cassette: Cassette,
+    transport: Arc<dyn Transport>,
+}
+
+impl Transport for CassetteTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            match self.cassette.mode {
+                CassetteMode::Replay => self.cassette.replay_interaction(&request).await,
+                CassetteMode::Record => {
+                    let response = self.transport.send(request.clone()).await?;
+                    self.cassette
+                        .record_interaction(&request, &response)
+                        .await?;
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+impl RecordedRequest {
+    fn new(request: &TransportRequest, secrets: &[String]) -> RecordedRequest {
+        let url = request
+            .url
+            .split_once("://")
+            .map(|(_, e)| e)
+            .unwrap_or(&request.url);
+        let path = url.find('/').map(|i| &url[i..]).unwrap_or("/");
+        let path = path.split_once('?').map(|(path, _)| path).unwrap_or(path);
+
+        RecordedRequest {
+            method: request.method.to_string(),
+            path: scrub(path, secrets),
+            query: request
+                .normalized_query()
+                .into_iter()
+                .map(|(k, v)| (k, scrub(&v, secrets)))
+                .collect(),
+            body: request.body.as_deref().map(|e| body_value(e, secrets)),
+        }
+    }
+}
+
+impl std::fmt::Display for RecordedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let query = self
+            .query
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        write!(f, "{} {}?{}", self.method, self.path, query)
+    }
+}
+
+impl RecordedResponse {
+    fn new(response: &TransportResponse, secrets: &[String]) -> RecordedResponse {
+        RecordedResponse {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter(|(name, _)| !SCRUBBED_HEADERS.contains(&name.as_str()))
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), scrub(value.to_str().ok()?, secrets)))
+                })
+                .collect(),
+            body: body_value(&response.body, secrets),
+        }
+    }
+
+    fn to_response(&self) -> Result<TransportResponse, Error> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value))
+            else {
+                continue;
+            };
+            headers.append(name, value);
+        }
+
+        let body = match &self.body {
+            Value::String(body) => body.clone().into_bytes(),
+            body => serde_json::to_vec(body)?,
+        };
+
+        Ok(TransportResponse {
+            status: StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            headers,
+            body,
+            not_modified: false,
+        })
+    }
+}
+
+/// Collect the credentials of a request
+fn secrets(headers: &HeaderMap) -> Vec<String> {
+    [header::AUTHORIZATION.as_str(), "media-user-token"]
+        .into_iter()
+        .filter_map(|name| headers.get(name)?.to_str().ok())
+        .map(|value| value.strip_prefix("Bearer ").unwrap_or(value).to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Replace all credentials in a string
+fn scrub(value: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(value.to_string(), |value, secret| {
+        value.replace(secret, REDACTED)
+    })
+}
+
+/// Store json bodies as json so recordings stay readable, anything else as a string
+fn body_value(body: &[u8], secrets: &[String]) -> Value {
+    let body = scrub(&String::from_utf8_lossy(body), secrets);
+    match serde_json::from_str::<Value>(&body) {
+        Ok(value) if !value.is_string() => value,
+        _ => Value::String(body),
+    }
+}
@@ -1,24 +1,82 @@
 //! Error types
 
 use crate::resource::ErrorResponse;
-use reqwest::header::InvalidHeaderValue;
+use crate::retry::parse_retry_after;
+use crate::transport::TransportResponse;
+use reqwest::header::{HeaderMap, InvalidHeaderValue};
+use reqwest::StatusCode;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
 use thiserror::Error;
 
+/// Headers that might carry the Apple request id of a response
+const REQUEST_ID_HEADERS: [&str; 2] = ["x-request-id", "x-apple-jingle-correlation-key"];
+
 /// Error type
 #[derive(Error, Debug)]
 pub enum Error {
     /// Missing resource data
     #[error("Missing resource data on a resource")]
     MissingResourceData,
+    /// A relationship or view was iterated without a request context
+    #[error("Missing request context, attach one with AttachContext::attach_context")]
+    MissingContext,
     /// Invalid resource type error
     #[error("Invalid resource type")]
     InvalidResourceType,
-    /// Apple music error
-    #[error("Apple music error: {0:#?}")]
-    MusicError(ErrorResponse),
+    /// The api answered `401 Unauthorized`
+    #[error("Unauthorized: {0}")]
+    Unauthorized(Box<ApiError>),
+    /// The api answered `403 Forbidden`
+    #[error("Forbidden: {0}")]
+    Forbidden(Box<ApiError>),
+    /// The api answered `404 Not Found`
+    #[error("Not found: {0}")]
+    NotFound(Box<ApiError>),
+    /// The api answered `429 Too Many Requests`
+    #[error("Rate limited: {error}")]
+    RateLimited {
+        /// Delay requested by the `Retry-After` header
+        retry_after: Option<Duration>,
+        /// Response details
+        error: Box<ApiError>,
+    },
+    /// The api answered with a `5xx` status
+    #[error("Server error: {0}")]
+    ServerError(Box<ApiError>),
+    /// The api answered with any other unsuccessful status
+    #[error("Unexpected response: {0}")]
+    Unexpected(Box<ApiError>),
+    /// No developer token or token provider was configured on a client builder
+    #[error("Missing developer token")]
+    MissingDeveloperToken,
+    /// A personalized endpoint was requested by a client without a media user token
+    #[error("Media user token is required for personalized endpoint {0}")]
+    MissingMediaUserToken(String),
+    /// Storefront id does not map to a known country
+    #[error("Unknown storefront {0}")]
+    UnknownStorefront(String),
+    /// The client is offline and the request was not found in its caches
+    #[error("Offline cache miss for {0}")]
+    CacheMiss(String),
+    /// Request not found in a replayed cassette
+    #[error("No recorded interaction in cassette for {0}")]
+    CassetteMiss(String),
+    /// A coalesced request failed, the error is shared by all requests waiting on it
+    #[error(transparent)]
+    Coalesced(std::sync::Arc<Error>),
+    /// A batched lookup was dropped before its batch was sent, for example when the runtime shut down
+    #[error("Batched lookup was cancelled")]
+    BatchCancelled,
     /// Invalid header value
     #[error("Invalid header value")]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
+    /// Invalid developer token private key
+    #[error("Invalid developer token private key: {0}")]
+    InvalidPrivateKey(#[from] p256::pkcs8::Error),
+    /// An io error occurred
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     /// A [`reqwest::Error`] occured
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
@@ -29,3 +87,106 @@ pub enum Error {
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 }
+
+impl Error {
+    /// Create an error from an unsuccessful response
+    pub fn from_response(response: TransportResponse) -> Error {
+        let retry_after = parse_retry_after(&response.headers);
+        let error = Box::new(ApiError::from(response));
+
+        match error.status {
+            StatusCode::UNAUTHORIZED => Error::Unauthorized(error),
+            StatusCode::FORBIDDEN => Error::Forbidden(error),
+            StatusCode::NOT_FOUND => Error::NotFound(error),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited { retry_after, error },
+            status if status.is_server_error() => Error::ServerError(error),
+            _ => Error::Unexpected(error),
+        }
+    }
+
+    /// Get the response details if this error was caused by an unsuccessful response
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::Unauthorized(error)
+            | Error::Forbidden(error)
+            | Error::NotFound(error)
+            | Error::RateLimited { error, .. }
+            | Error::ServerError(error)
+            | Error::Unexpected(error) => Some(error.as_ref()),
+            Error::Coalesced(error) => error.api_error(),
+            _ => None,
+        }
+    }
+
+    /// Check if the failed request is worth retrying later
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::RateLimited { .. } | Error::ServerError(_) => true,
+            Error::Reqwest(error) => error.is_timeout() || error.is_connect(),
+            Error::Coalesced(error) => error.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Details of an unsuccessful api response
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// Http status
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Apple request id, taken from the response headers or the id of the first error
+    pub request_id: Option<String>,
+    /// Raw response body
+    pub body: String,
+    /// Apple music errors, [`None`] if the body was not an error response
+    pub response: Option<ErrorResponse>,
+}
+
+impl From<TransportResponse> for ApiError {
+    fn from(response: TransportResponse) -> Self {
+        let error_response = response
+            .json::<ErrorResponse>()
+            .ok()
+            .filter(|e| !e.errors.is_empty() || e.code.is_some());
+
+        let request_id = REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| response.headers.get(*name)?.to_str().ok())
+            .map(str::to_string)
+            .or_else(|| {
+                let error = error_response.as_ref()?.errors.first()?;
+                Some(error.id.clone()).filter(|e| !e.is_empty())
+            });
+
+        ApiError {
+            status: response.status,
+            request_id,
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+            headers: response.headers,
+            response: error_response,
+        }
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status)?;
+
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request id {request_id})")?;
+        }
+
+        let detail = self
+            .response
+            .as_ref()
+            .and_then(|e| e.errors.first())
+            .map(|e| e.detail.as_str())
+            .filter(|e| !e.is_empty());
+        match detail {
+            Some(detail) => write!(f, ": {detail}"),
+            None => Ok(()),
+        }
+    }
+}
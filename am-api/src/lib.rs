@@ -1,15 +1,47 @@
 //! Apple music api
 #![deny(missing_docs)]
 
+use crate::cache::conditional::ConditionalCache;
+use crate::cache::disk::DiskCache;
+use crate::cache::{CacheKey, ResponseCache};
+use crate::cassette::Cassette;
 use crate::error::Error;
+use crate::middleware::{Middleware, Next};
+use crate::observer::{Observer, RequestEvent, ResponseEvent};
+use crate::rate_limit::{RateLimiter, RouteKind};
+use crate::resource::storefront::Storefront;
+use crate::retry::RetryPolicy;
+use crate::single_flight::SingleFlight;
+use crate::token::developer::{DeveloperToken, DeveloperTokenProvider};
+use crate::token::{StaticTokenProvider, TokenProvider};
+use crate::transport::{
+    RequestBuilder, ReqwestTransport, Transport, TransportRequest, TransportResponse,
+    DEFAULT_BASE_URL,
+};
 pub use celes;
-use reqwest::{header, RequestBuilder};
+use reqwest::{header, Method, StatusCode};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+pub mod cassette;
 pub mod error;
+pub mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod observer;
 pub mod primitive;
+pub mod rate_limit;
 pub mod request;
 pub mod resource;
+pub mod retry;
+mod single_flight;
 pub mod time;
+pub mod token;
+mod trace;
+pub mod transport;
 
 /// Cast a Resource to a more specific type
 ///
@@ -32,41 +64,106 @@ macro_rules! cast {
 
 /// Apple music api client
 ///
-/// Api client can be cloned safely as reqwest uses Arc internally
+/// Api client can be cloned safely as the transport is shared behind an [`Arc`]
 #[derive(Clone)]
 pub struct ApiClient {
-    client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    base_url: String,
+    token_provider: Arc<dyn TokenProvider>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    disk_cache: Option<Arc<DiskCache>>,
+    conditional_cache: Option<Arc<ConditionalCache>>,
+    offline: bool,
+    single_flight: Option<Arc<SingleFlight>>,
+    observers: Vec<Arc<dyn Observer>>,
+    middleware: Vec<Arc<dyn Middleware>>,
     storefront_country: celes::Country,
     localization: String,
 }
 
 impl ApiClient {
+    /// Create a new [`ApiClientBuilder`] instance
+    pub fn builder() -> ApiClientBuilder {
+        ApiClientBuilder::new()
+    }
+
     /// Create a new [`ApiClient`] instance
     pub fn new(
         developer_token: &str,
         media_user_token: &str,
         storefront_country: celes::Country,
     ) -> Result<ApiClient, Error> {
-        let mut headers = header::HeaderMap::new();
+        ApiClientBuilder::new()
+            .developer_token(developer_token)
+            .media_user_token(media_user_token)
+            .storefront_country(storefront_country)
+            .build()
+    }
 
-        let mut authorization_header =
-            header::HeaderValue::from_str(&format!("Bearer {}", developer_token))?;
-        authorization_header.set_sensitive(true);
-        headers.insert(header::AUTHORIZATION, authorization_header);
+    /// Create a new catalog-only [`ApiClient`] instance without a media user token
+    ///
+    /// Requests to personalized `/v1/me` endpoints fail with [`Error::MissingMediaUserToken`]
+    pub fn new_catalog_only(
+        developer_token: &str,
+        storefront_country: celes::Country,
+    ) -> Result<ApiClient, Error> {
+        ApiClientBuilder::new()
+            .developer_token(developer_token)
+            .storefront_country(storefront_country)
+            .build()
+    }
 
-        let mut media_user_token_header = header::HeaderValue::from_str(media_user_token)?;
-        media_user_token_header.set_sensitive(true);
-        headers.insert("media-user-token", media_user_token_header);
+    /// Create a new [`ApiClient`] instance that sends requests using a custom [`Transport`]
+    pub fn with_transport(
+        developer_token: &str,
+        media_user_token: &str,
+        storefront_country: celes::Country,
+        transport: impl Transport + 'static,
+    ) -> Result<ApiClient, Error> {
+        ApiClientBuilder::new()
+            .developer_token(developer_token)
+            .media_user_token(media_user_token)
+            .storefront_country(storefront_country)
+            .transport(transport)
+            .build()
+    }
 
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()?;
+    /// Create a new [`ApiClient`] instance that signs developer tokens from a MusicKit private key
+    pub fn with_developer_token(
+        developer_token: DeveloperToken,
+        media_user_token: &str,
+        storefront_country: celes::Country,
+    ) -> Result<ApiClient, Error> {
+        ApiClientBuilder::new()
+            .signed_developer_token(developer_token)
+            .media_user_token(media_user_token)
+            .storefront_country(storefront_country)
+            .build()
+    }
 
-        Ok(ApiClient {
-            client,
-            storefront_country,
-            localization: String::from("en-US"),
-        })
+    /// Create a new [`ApiClient`] instance that requests tokens from a [`TokenProvider`]
+    pub fn with_token_provider(
+        token_provider: impl TokenProvider + 'static,
+        storefront_country: celes::Country,
+    ) -> Result<ApiClient, Error> {
+        ApiClientBuilder::new()
+            .token_provider(token_provider)
+            .storefront_country(storefront_country)
+            .build()
+    }
+
+    /// Get the token provider used for authorization
+    pub fn get_token_provider(&self) -> &dyn TokenProvider {
+        self.token_provider.as_ref()
+    }
+
+    /// Set the token provider used for authorization
+    ///
+    /// The provider is shared with all clones made from this client afterwards
+    pub fn set_token_provider(&mut self, token_provider: impl TokenProvider + 'static) {
+        self.token_provider = Arc::new(token_provider);
     }
 
     /// Get the default storefront country for this client
@@ -74,6 +171,30 @@ impl ApiClient {
         self.storefront_country
     }
 
+    /// Set the default storefront country for this client
+    pub fn set_storefront_country(&mut self, storefront_country: celes::Country) {
+        self.storefront_country = storefront_country;
+    }
+
+    /// Fetch the user's storefront and use it as the default storefront country and localization for this client
+    pub async fn use_user_storefront(&mut self) -> Result<Storefront, Error> {
+        let storefront = Storefront::get()
+            .me(self)
+            .await?
+            .ok_or(Error::MissingResourceData)?;
+
+        let storefront_country = storefront
+            .country()
+            .ok_or_else(|| Error::UnknownStorefront(storefront.header.id.clone()))?;
+        self.set_storefront_country(storefront_country);
+
+        if let Some(attributes) = &storefront.attributes {
+            self.set_localization(&attributes.default_language_tag);
+        }
+
+        Ok(storefront)
+    }
+
     /// Get the default localization for this client
     pub fn get_localization(&self) -> &str {
         self.localization.as_str()
@@ -84,31 +205,702 @@ impl ApiClient {
         self.localization = localization.to_string();
     }
 
+    /// Get the base url requests are sent to
+    pub fn get_base_url(&self) -> &str {
+        self.base_url.as_str()
+    }
+
+    /// Set the base url requests are sent to
+    ///
+    /// Defaults to [`DEFAULT_BASE_URL`]
+    pub fn set_base_url(&mut self, base_url: &str) {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+    }
+
+    /// Get the retry policy used for requests
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Set the retry policy used for requests
+    ///
+    /// Defaults to [`RetryPolicy::default`], use [`RetryPolicy::none`] to disable retries
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Get the rate limiter used for requests
+    pub fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Set the rate limiter used for requests
+    ///
+    /// The limiter is shared with all clones made from this client afterwards
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+    }
+
+    /// Get the response cache used for requests
+    pub fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
+    /// Set the response cache used for requests
+    ///
+    /// The cache is shared with all clones made from this client afterwards
+    pub fn set_cache(&mut self, cache: ResponseCache) {
+        self.cache = Some(Arc::new(cache));
+    }
+
+    /// Get the on-disk response cache used for requests
+    pub fn get_disk_cache(&self) -> Option<&DiskCache> {
+        self.disk_cache.as_deref()
+    }
+
+    /// Set the on-disk response cache used for requests
+    ///
+    /// Failed writes to the cache are ignored, the response is still returned
+    pub fn set_disk_cache(&mut self, disk_cache: DiskCache) {
+        self.disk_cache = Some(Arc::new(disk_cache));
+    }
+
+    /// Get the conditional cache used to revalidate requests
+    pub fn get_conditional_cache(&self) -> Option<&ConditionalCache> {
+        self.conditional_cache.as_deref()
+    }
+
+    /// Set the conditional cache used to revalidate requests
+    ///
+    /// The cache is shared with all clones made from this client afterwards
+    pub fn set_conditional_cache(&mut self, conditional_cache: ConditionalCache) {
+        self.conditional_cache = Some(Arc::new(conditional_cache));
+    }
+
+    /// Check if this client is offline
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Set if this client is offline
+    ///
+    /// Offline clients only serve requests from their caches,
+    /// requests that are not cached fail with [`Error::CacheMiss`]
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// Check if concurrent identical `GET` requests are coalesced
+    pub fn is_coalescing_requests(&self) -> bool {
+        self.single_flight.is_some()
+    }
+
+    /// Set if concurrent identical `GET` requests are coalesced
+    ///
+    /// Coalesced requests share one network round-trip, every caller still parses its own copy of the response.
+    /// Personalized routes under `/v1/me` are never coalesced
+    pub fn set_coalesce_requests(&mut self, coalesce_requests: bool) {
+        self.single_flight = coalesce_requests.then(Arc::default);
+    }
+
+    /// Record requests to a cassette or replay them from it
+    ///
+    /// The cassette wraps the current transport, in replay mode no request reaches it
+    pub fn set_cassette(&mut self, cassette: Cassette) {
+        self.transport = Arc::new(cassette.wrap(self.transport.clone()));
+    }
+
+    /// Register an observer notified for every http call sent by this client
+    ///
+    /// Observers are shared with all clones made from this client afterwards
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Arc::new(observer));
+    }
+
+    /// Append a middleware to the end of this client's middleware chain
+    ///
+    /// The middleware is shared with all clones made from this client afterwards
+    pub fn add_middleware(&mut self, middleware: impl Middleware + 'static) {
+        self.middleware.push(Arc::new(middleware));
+    }
+
     /// Convenience method to make a GET request to an endpoint
-    pub fn get(&self, endpoint: &str) -> RequestBuilder {
-        self.client
-            .get(format!("https://api.music.apple.com{}", endpoint))
-            .query(&[("art[url]", "f")])
+    pub fn get(&self, endpoint: &str) -> RequestBuilder<'_> {
+        self.request(Method::GET, endpoint)
     }
 
     /// Convenience method to make a POST request to an endpoint
-    pub fn post(&self, endpoint: &str) -> RequestBuilder {
-        self.client
-            .post(format!("https://api.music.apple.com{}", endpoint))
-            .query(&[("art[url]", "f")])
+    pub fn post(&self, endpoint: &str) -> RequestBuilder<'_> {
+        self.request(Method::POST, endpoint)
     }
 
     /// Convenience method to make a PUT request to an endpoint
-    pub fn put(&self, endpoint: &str) -> RequestBuilder {
-        self.client
-            .put(format!("https://api.music.apple.com{}", endpoint))
-            .query(&[("art[url]", "f")])
+    pub fn put(&self, endpoint: &str) -> RequestBuilder<'_> {
+        self.request(Method::PUT, endpoint)
     }
 
     /// Convenience method to make a DELETE request to an endpoint
-    pub fn delete(&self, endpoint: &str) -> RequestBuilder {
-        self.client
-            .delete(format!("https://api.music.apple.com{}", endpoint))
-            .query(&[("art[url]", "f")])
+    pub fn delete(&self, endpoint: &str) -> RequestBuilder<'_> {
+        self.request(Method::DELETE, endpoint)
+    }
+
+    /// Make a request to an endpoint
+    pub fn request(&self, method: Method, endpoint: &str) -> RequestBuilder<'_> {
+        let request = TransportRequest {
+            method,
+            url: format!("{}{}", self.base_url, endpoint),
+            query: Vec::new(),
+            headers: header::HeaderMap::new(),
+            body: None,
+        };
+
+        RequestBuilder::new(self, request).query(&[("art[url]", "f")])
+    }
+
+    /// Send a request through this client's [`Middleware`] chain and transport
+    ///
+    /// Authorization headers are requested from the client's [`TokenProvider`] for every attempt,
+    /// a request rejected with `401 Unauthorized` or `403 Forbidden` is replayed once if refreshing the tokens changed them.
+    /// Every attempt waits for capacity on the client's [`RateLimiter`],
+    /// failed attempts are retried according to the client's [`RetryPolicy`].
+    /// `GET` requests are served from the client's [`ResponseCache`] and [`DiskCache`] when possible
+    pub async fn execute(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        self.execute_with_cache(request, true).await
+    }
+
+    /// Send a request through the client's [`Middleware`] chain, looking it up in the client's
+    /// [`ResponseCache`] and [`DiskCache`] first when `use_cache` is set
+    pub(crate) async fn execute_with_cache(
+        &self,
+        request: TransportRequest,
+        use_cache: bool,
+    ) -> Result<TransportResponse, Error> {
+        Next::new(self, &self.middleware, use_cache)
+            .run(request)
+            .await
+    }
+
+    /// Send a request using this client's transport, looking it up in the client's [`ResponseCache`]
+    /// and [`DiskCache`] first when `use_cache` is set
+    pub(crate) async fn fetch(
+        &self,
+        request: TransportRequest,
+        use_cache: bool,
+    ) -> Result<TransportResponse, Error> {
+        let Some(mut key) = CacheKey::new(&request, &self.base_url) else {
+            if self.offline {
+                return Err(Error::CacheMiss(request.path(&self.base_url).to_string()));
+            }
+            return self.send(request).await;
+        };
+
+        if RouteKind::from_path(&key.path) == RouteKind::Personalized {
+            if let Some(media_user_token) = self.token_provider.media_user_token().await? {
+                key = key.with_user(&media_user_token);
+            }
+        }
+
+        if use_cache {
+            if let Some(response) = self.cache.as_ref().and_then(|cache| cache.get(&key)) {
+                return Ok(response);
+            }
+
+            if let Some(disk_cache) = &self.disk_cache {
+                if let Some(response) = disk_cache.get(&key).await {
+                    if let Some(cache) = &self.cache {
+                        cache.insert(key, &response);
+                    }
+                    return Ok(response);
+                }
+            }
+        }
+
+        if self.offline {
+            return Err(Error::CacheMiss(key.path));
+        }
+
+        // personalized responses belong to the user that requested them and are never shared
+        let single_flight = self
+            .single_flight
+            .as_ref()
+            .filter(|_| RouteKind::from_path(&key.path) == RouteKind::Catalog);
+        let response = match single_flight {
+            Some(single_flight) => {
+                let client = self.clone();
+                let request_key = key.clone();
+                single_flight
+                    .run(key.clone(), async move {
+                        client.send_conditional(request, &request_key).await
+                    })
+                    .await?
+            }
+            None => self.send_conditional(request, &key).await?,
+        };
+
+        if use_cache {
+            if let Some(disk_cache) = &self.disk_cache {
+                // the disk cache is best-effort, a failed write doesn't fail the request
+                if let Err(e) = disk_cache.insert(&key, &response).await {
+                    trace::record_disk_cache_error(&key.path, &e);
+                }
+            }
+            if let Some(cache) = &self.cache {
+                cache.insert(key, &response);
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send a request with the validators of a previous response from the client's [`ConditionalCache`]
+    async fn send_conditional(
+        &self,
+        mut request: TransportRequest,
+        key: &CacheKey,
+    ) -> Result<TransportResponse, Error> {
+        let Some(conditional_cache) = &self.conditional_cache else {
+            return self.send(request).await;
+        };
+
+        let previous = conditional_cache.get(key);
+        if let Some(previous) = &previous {
+            if let Some(etag) = previous.headers.get(header::ETAG) {
+                request.headers.insert(header::IF_NONE_MATCH, etag.clone());
+            }
+            if let Some(last_modified) = previous.headers.get(header::LAST_MODIFIED) {
+                request
+                    .headers
+                    .insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        let response = self.send(request).await?;
+
+        match previous {
+            Some(mut previous) if response.status == StatusCode::NOT_MODIFIED => {
+                previous.not_modified = true;
+                Ok(previous)
+            }
+            _ => {
+                conditional_cache.insert(key.clone(), &response);
+                Ok(response)
+            }
+        }
+    }
+
+    /// Send a request, applying authorization, rate limits and retries
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, Error> {
+        let span = trace::request_span(&request, &self.base_url);
+        trace::instrument(self.send_attempts(request, &span), &span).await
+    }
+
+    /// Send a request until it succeeds or runs out of retries
+    async fn send_attempts(
+        &self,
+        request: TransportRequest,
+        span: &trace::Span,
+    ) -> Result<TransportResponse, Error> {
+        let route = RouteKind::from_path(request.path(&self.base_url));
+        let mut attempt = 1;
+        let mut refreshed = false;
+
+        loop {
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire(route).await;
+            }
+
+            let tokens = self.tokens().await?;
+            let mut request = request.clone();
+            self.authorize(&mut request, route, &tokens)?;
+
+            trace::record_attempt(span, attempt, &request);
+            let event = RequestEvent::new(&request, &self.base_url, attempt);
+            for observer in &self.observers {
+                observer.on_request(&event);
+            }
+
+            let method = request.method.clone();
+            let start = Instant::now();
+            let result = self.transport.send(request).await;
+            let latency = start.elapsed();
+
+            trace::record_response(span, &result, latency);
+            if !self.observers.is_empty() {
+                let event = ResponseEvent::new(event, &result, latency);
+                for observer in &self.observers {
+                    observer.on_response(&event);
+                }
+            }
+
+            let rejected = matches!(
+                &result,
+                Ok(response) if response.status == StatusCode::UNAUTHORIZED
+                    || response.status == StatusCode::FORBIDDEN
+            );
+
+            if rejected && !refreshed {
+                refreshed = true;
+                self.token_provider.refresh().await?;
+
+                // replaying with unchanged tokens would only be rejected again
+                if self.tokens().await? != tokens {
+                    continue;
+                }
+            }
+
+            let Some(delay) = self.retry_policy.retry_delay(&method, attempt, &result) else {
+                return result;
+            };
+
+            trace::record_retry(span, attempt, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Get the developer and media user tokens from the client's [`TokenProvider`]
+    async fn tokens(&self) -> Result<(String, Option<String>), Error> {
+        Ok((
+            self.token_provider.developer_token().await?,
+            self.token_provider.media_user_token().await?,
+        ))
+    }
+
+    /// Set authorization headers on a request
+    fn authorize(
+        &self,
+        request: &mut TransportRequest,
+        route: RouteKind,
+        (developer_token, media_user_token): &(String, Option<String>),
+    ) -> Result<(), Error> {
+        let mut authorization_header =
+            header::HeaderValue::from_str(&format!("Bearer {}", developer_token))?;
+        authorization_header.set_sensitive(true);
+        request
+            .headers
+            .insert(header::AUTHORIZATION, authorization_header);
+
+        match media_user_token {
+            Some(media_user_token) => {
+                let mut media_user_token_header = header::HeaderValue::from_str(media_user_token)?;
+                media_user_token_header.set_sensitive(true);
+                request
+                    .headers
+                    .insert("media-user-token", media_user_token_header);
+            }
+            None if route == RouteKind::Personalized => {
+                return Err(Error::MissingMediaUserToken(
+                    request.path(&self.base_url).to_string(),
+                ));
+            }
+            None => {
+                request.headers.remove("media-user-token");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Api client builder
+pub struct ApiClientBuilder {
+    developer_token: Option<String>,
+    signed_developer_token: Option<DeveloperToken>,
+    media_user_token: Option<String>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    transport: Option<Arc<dyn Transport>>,
+    reqwest_client: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<reqwest::Proxy>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    disk_cache: Option<DiskCache>,
+    conditional_cache: Option<ConditionalCache>,
+    offline: bool,
+    coalesce_requests: bool,
+    observers: Vec<Arc<dyn Observer>>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    cassette: Option<Cassette>,
+    storefront_country: celes::Country,
+    localization: String,
+}
+
+impl ApiClientBuilder {
+    /// Create a new [`ApiClientBuilder`] instance
+    pub fn new() -> ApiClientBuilder {
+        ApiClientBuilder {
+            developer_token: None,
+            signed_developer_token: None,
+            media_user_token: None,
+            token_provider: None,
+            transport: None,
+            reqwest_client: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            proxy: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            cache: None,
+            disk_cache: None,
+            conditional_cache: None,
+            offline: false,
+            coalesce_requests: false,
+            observers: Vec::new(),
+            middleware: Vec::new(),
+            cassette: None,
+            storefront_country: celes::Country::the_united_states_of_america(),
+            localization: String::from("en-US"),
+        }
+    }
+
+    /// Set a fixed developer token
+    pub fn developer_token(mut self, developer_token: &str) -> Self {
+        self.developer_token = Some(developer_token.to_string());
+        self
+    }
+
+    /// Sign developer tokens from a MusicKit private key
+    pub fn signed_developer_token(mut self, developer_token: DeveloperToken) -> Self {
+        self.signed_developer_token = Some(developer_token);
+        self
+    }
+
+    /// Set a fixed media user token
+    ///
+    /// Without a media user token the client is catalog-only
+    pub fn media_user_token(mut self, media_user_token: &str) -> Self {
+        self.media_user_token = Some(media_user_token.to_string());
+        self
+    }
+
+    /// Request tokens from a [`TokenProvider`], this takes precedence over fixed tokens
+    pub fn token_provider(mut self, token_provider: impl TokenProvider + 'static) -> Self {
+        self.token_provider = Some(Arc::new(token_provider));
+        self
+    }
+
+    /// Send requests using a custom [`Transport`]
+    ///
+    /// This takes precedence over all reqwest options
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Send requests using an already configured [`reqwest::Client`]
+    ///
+    /// This takes precedence over timeout, user agent, proxy and pool options
+    pub fn reqwest_client(mut self, client: reqwest::Client) -> Self {
+        self.reqwest_client = Some(client);
+        self
+    }
+
+    /// Set a total timeout for every request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a timeout for the connect phase of every request
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the `User-Agent` header sent with every request
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Send requests through a proxy
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Set the maximum amount of idle connections kept per host
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the timeout after which idle connections are closed
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the base url requests are sent to
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the retry policy
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the rate limiter
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Set the response cache
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set the on-disk response cache
+    pub fn disk_cache(mut self, disk_cache: DiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Set the conditional cache used to revalidate requests
+    pub fn conditional_cache(mut self, conditional_cache: ConditionalCache) -> Self {
+        self.conditional_cache = Some(conditional_cache);
+        self
+    }
+
+    /// Coalesce concurrent identical `GET` requests into one network round-trip
+    pub fn coalesce_requests(mut self, coalesce_requests: bool) -> Self {
+        self.coalesce_requests = coalesce_requests;
+        self
+    }
+
+    /// Record requests to a cassette or replay them from it
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Register an observer notified for every http call
+    pub fn observer(mut self, observer: impl Observer + 'static) -> Self {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Append a middleware to the end of the middleware chain
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Only serve requests from the client's caches
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Set the default storefront country
+    pub fn storefront_country(mut self, storefront_country: celes::Country) -> Self {
+        self.storefront_country = storefront_country;
+        self
+    }
+
+    /// Set the default localization
+    pub fn localization(mut self, localization: &str) -> Self {
+        self.localization = localization.to_string();
+        self
+    }
+
+    /// Build the [`ApiClient`] using the storefront of the user as the default storefront country and localization
+    ///
+    /// See [`ApiClient::use_user_storefront`]
+    pub async fn build_with_user_storefront(self) -> Result<ApiClient, Error> {
+        let mut client = self.build()?;
+        client.use_user_storefront().await?;
+        Ok(client)
+    }
+
+    /// Build the [`ApiClient`]
+    pub fn build(self) -> Result<ApiClient, Error> {
+        let token_provider: Arc<dyn TokenProvider> = match (
+            self.token_provider,
+            self.signed_developer_token,
+            self.developer_token,
+        ) {
+            (Some(token_provider), _, _) => token_provider,
+            (None, Some(developer_token), _) => Arc::new(DeveloperTokenProvider::new(
+                developer_token,
+                self.media_user_token.as_deref(),
+            )?),
+            (None, None, Some(developer_token)) => Arc::new(StaticTokenProvider::new(
+                &developer_token,
+                self.media_user_token.as_deref(),
+            )?),
+            (None, None, None) => return Err(Error::MissingDeveloperToken),
+        };
+
+        let transport: Arc<dyn Transport> = match (self.transport, self.reqwest_client) {
+            (Some(transport), _) => transport,
+            (None, Some(client)) => Arc::new(ReqwestTransport::new(client)),
+            (None, None) => {
+                let mut builder = reqwest::Client::builder();
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+
+                Arc::new(ReqwestTransport::new(builder.build()?))
+            }
+        };
+        let transport = match self.cassette {
+            Some(cassette) => Arc::new(cassette.wrap(transport)),
+            None => transport,
+        };
+
+        Ok(ApiClient {
+            transport,
+            base_url: self.base_url,
+            token_provider,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter.map(Arc::new),
+            cache: self.cache.map(Arc::new),
+            disk_cache: self.disk_cache.map(Arc::new),
+            conditional_cache: self.conditional_cache.map(Arc::new),
+            offline: self.offline,
+            single_flight: self.coalesce_requests.then(Arc::default),
+            observers: self.observers,
+            middleware: self.middleware,
+            storefront_country: self.storefront_country,
+            localization: self.localization,
+        })
+    }
+}
+
+impl Default for ApiClientBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
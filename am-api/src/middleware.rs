@@ -0,0 +1,92 @@
+//! Request middleware
+
+use crate::error::Error;
+use crate::transport::{TransportRequest, TransportResponse};
+use crate::ApiClient;
+use futures::future::BoxFuture;
+use std::sync::Arc;
+
+/// Layer wrapping every request sent by an [`ApiClient`]
+///
+/// Middleware can inspect or modify the outgoing request before passing it to [`Next::run`],
+/// and inspect or modify the response before it is parsed. A middleware can also
+/// short-circuit the chain by returning a response without calling [`Next::run`].
+///
+/// Middleware runs in the order it was added, before the client's caches,
+/// so a request modified by a middleware is cached under its modified path
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// struct RequestId;
+///
+/// impl Middleware for RequestId {
+///     fn handle<'a>(
+///         &'a self,
+///         mut request: TransportRequest,
+///         next: Next<'a>,
+///     ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+///         request.headers.insert("x-request-id", HeaderValue::from_static("1"));
+///         next.run(request)
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Handle a request, passing it on to the rest of the chain with [`Next::run`]
+    fn handle<'a>(
+        &'a self,
+        request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>>;
+}
+
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn handle<'a>(
+        &'a self,
+        request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        self.as_ref().handle(request, next)
+    }
+}
+
+/// Remaining middleware chain of a request
+pub struct Next<'a> {
+    client: &'a ApiClient,
+    middleware: &'a [Arc<dyn Middleware>],
+    use_cache: bool,
+}
+
+impl<'a> Next<'a> {
+    /// Create a new [`Next`] instance
+    pub(crate) fn new(
+        client: &'a ApiClient,
+        middleware: &'a [Arc<dyn Middleware>],
+        use_cache: bool,
+    ) -> Next<'a> {
+        Next {
+            client,
+            middleware,
+            use_cache,
+        }
+    }
+
+    /// Get the client sending this request
+    pub fn client(&self) -> &'a ApiClient {
+        self.client
+    }
+
+    /// Run the rest of the chain, ending with the client's caches and transport
+    pub fn run(self, request: TransportRequest) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    middleware: rest,
+                    ..self
+                },
+            ),
+            None => Box::pin(self.client.fetch(request, self.use_cache)),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! In-process mock Apple Music api server
+//!
+//! The server speaks http on a local port, so every request builder works against it unchanged
+//!
+//! # Examples
+//!
+//! ```no_run,ignore
+//! let server = MockServer::start().await?;
+//! server.insert(song)?;
+//!
+//! let client = server.client()?;
+//! let song = Song::get().one(&client, "1").await?;
+//!
+//! LibraryAddResourceBuilder::new().add_resource(&song.into())?.send(&client).await?;
+//! ```
+
+use crate::error::Error;
+use crate::resource::Resource;
+use crate::ApiClient;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::oneshot;
+
+mod router;
+
+/// Developer token accepted by the mock server clients
+pub const MOCK_DEVELOPER_TOKEN: &str = "mock-developer-token";
+
+/// Media user token accepted by the mock server clients
+pub const MOCK_MEDIA_USER_TOKEN: &str = "mock-media-user-token";
+
+/// Local http server faking the Apple Music api
+///
+/// Serves seeded catalog resources, catalog and library search, a mutable library with playlists and ratings,
+/// listening history, personal recommendations, `offset`/`limit` pagination with `next` links, and Apple shaped [`ErrorResponse`](crate::resource::ErrorResponse) bodies.
+///
+/// The server stops when dropped
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+/// Mutable state of a [`MockServer`]
+#[derive(Debug, Default)]
+struct MockState {
+    /// Catalog resources by type and id
+    catalog: HashMap<(String, String), Value>,
+    /// Library resources by type, in the order they were added
+    library: HashMap<String, Vec<Value>>,
+    /// Played resources, in the order they were played
+    played: Vec<Value>,
+    /// Ratings by resource type and id
+    ratings: HashMap<(String, String), Value>,
+    /// Programmed responses by method and path
+    responses: HashMap<(Method, String), (StatusCode, Value)>,
+    /// Counter used to generate library ids
+    next_id: u64,
+}
+
+impl MockServer {
+    /// Start a new [`MockServer`] instance on a random local port
+    pub async fn start() -> Result<MockServer, Error> {
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let (shutdown, shutdown_receiver) = oneshot::channel::<()>();
+
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let state = state.clone();
+                    async move { Ok::<_, Infallible>(handle(&state, request).await) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .map_err(std::io::Error::other)?
+            .serve(make_service);
+        let address = server.local_addr();
+
+        tokio::spawn(server.with_graceful_shutdown(async {
+            let _ = shutdown_receiver.await;
+        }));
+
+        Ok(MockServer {
+            address,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Get the base url of this server
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Create an [`ApiClient`] pointing at this server
+    pub fn client(&self) -> Result<ApiClient, Error> {
+        ApiClient::builder()
+            .developer_token(MOCK_DEVELOPER_TOKEN)
+            .media_user_token(MOCK_MEDIA_USER_TOKEN)
+            .base_url(&self.url())
+            .build()
+    }
+
+    /// Seed a catalog resource or personal recommendation, replacing a previous resource with the same type and id
+    ///
+    /// Requests without `ids` list the seeded resources of the requested type
+    pub fn insert(&self, resource: impl Into<Resource>) -> Result<(), Error> {
+        let resource = serde_json::to_value(resource.into())?;
+        let key = router::resource_key(&resource);
+        self.lock().catalog.insert(key, resource);
+        Ok(())
+    }
+
+    /// Seed a library resource, its type has to be one of the `library-*` types
+    pub fn insert_library(&self, resource: impl Into<Resource>) -> Result<(), Error> {
+        let resource = serde_json::to_value(resource.into())?;
+        let (ty, _) = router::resource_key(&resource);
+        self.lock().library.entry(ty).or_default().push(resource);
+        Ok(())
+    }
+
+    /// Record a play of a resource, feeding the recently played and heavy rotation history
+    pub fn insert_played(&self, resource: impl Into<Resource>) -> Result<(), Error> {
+        let resource = serde_json::to_value(resource.into())?;
+        self.lock().played.push(resource);
+        Ok(())
+    }
+
+    /// Get the library resources of a type, for example `library-songs`
+    pub fn library(&self, ty: &str) -> Vec<Value> {
+        self.lock().library.get(ty).cloned().unwrap_or_default()
+    }
+
+    /// Always answer requests to `path` with the given status and json body
+    ///
+    /// `path` is matched exactly, without the query string
+    pub fn respond(&self, method: Method, path: &str, status: StatusCode, body: Value) {
+        self.lock()
+            .responses
+            .insert((method, path.to_string()), (status, body));
+    }
+
+    /// Remove all seeded resources, library contents and programmed responses
+    pub fn reset(&self) {
+        *self.lock() = MockState::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Read a request and route it
+async fn handle(state: &Mutex<MockState>, request: Request<Body>) -> Response<Body> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let request = router::MockRequest::new(&parts, &body);
+
+    let (status, body) = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        router::route(&mut state, &request)
+    };
+
+    let mut response = Response::builder().status(status.as_u16());
+    let body = match body {
+        Some(body) => {
+            response = response.header("content-type", "application/json");
+            Body::from(serde_json::to_vec(&body).unwrap_or_default())
+        }
+        None => Body::empty(),
+    };
+
+    response
+        .body(body)
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
@@ -0,0 +1,618 @@
+//! Mock server routes
+
+use super::MockState;
+use hyper::http::request::Parts;
+use reqwest::{header, Method, StatusCode};
+use serde_json::{json, Value};
+
+/// Default amount of resources per page
+const DEFAULT_LIMIT: usize = 25;
+
+/// Resource types that can be added to the library
+const LIBRARY_TYPES: [&str; 5] = ["albums", "artists", "music-videos", "playlists", "songs"];
+
+/// Resource types reported as played tracks rather than played containers
+const TRACK_TYPES: [&str; 4] = [
+    "library-music-videos",
+    "library-songs",
+    "music-videos",
+    "songs",
+];
+
+/// Route result, a status with an optional json body
+pub(super) type MockResponse = (StatusCode, Option<Value>);
+
+/// Parsed mock server request
+pub(super) struct MockRequest {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    authorized: bool,
+    media_user_token: bool,
+    body: Option<Value>,
+}
+
+impl MockRequest {
+    /// Create a new [`MockRequest`] instance
+    pub(super) fn new(parts: &Parts, body: &[u8]) -> MockRequest {
+        let query = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|e| e.split_once('='))
+            .map(|(k, v)| (decode(k), decode(v)))
+            .collect();
+
+        MockRequest {
+            method: parts.method.clone(),
+            path: parts.uri.path().to_string(),
+            query,
+            authorized: parts
+                .headers
+                .get(header::AUTHORIZATION)
+                .is_some_and(|e| e.as_bytes().starts_with(b"Bearer ")),
+            media_user_token: parts.headers.contains_key("media-user-token"),
+            body: serde_json::from_slice(body).ok(),
+        }
+    }
+
+    fn query_value(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn ids(&self, key: &str) -> Option<Vec<&str>> {
+        self.query_value(key)
+            .map(|e| e.split(',').filter(|e| !e.is_empty()).collect())
+    }
+}
+
+/// Get the type and id of a resource
+pub(super) fn resource_key(resource: &Value) -> (String, String) {
+    let field = |name: &str| resource[name].as_str().unwrap_or_default().to_string();
+    (field("type"), field("id"))
+}
+
+/// Route a request
+pub(super) fn route(state: &mut MockState, request: &MockRequest) -> MockResponse {
+    if let Some((status, body)) = state
+        .responses
+        .get(&(request.method.clone(), request.path.clone()))
+    {
+        return (*status, Some(body.clone()));
+    }
+
+    if !request.authorized {
+        return error(StatusCode::UNAUTHORIZED, "40100", "Unauthorized");
+    }
+
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    if segments.get(1) == Some(&"me") && !request.media_user_token {
+        return error(StatusCode::FORBIDDEN, "40300", "Forbidden");
+    }
+
+    match (&request.method, segments.as_slice()) {
+        (&Method::GET, ["v1", "catalog", _, "search"]) => {
+            search(request, |ty| catalog_resources(state, ty))
+        }
+        (&Method::GET, ["v1", "catalog", _, ty]) | (&Method::GET, ["v1", ty @ "storefronts"]) => {
+            catalog_many(state, request, ty)
+        }
+        (&Method::GET, ["v1", "catalog", _, ty, id])
+        | (&Method::GET, ["v1", ty @ "storefronts", id]) => {
+            found(state.catalog.get(&key(ty, id)).cloned())
+        }
+        (&Method::GET, ["v1", "catalog", _, ty, id, "view", name]) => {
+            match state.catalog.get(&key(ty, id)) {
+                Some(resource) => view(request, &resource["views"][*name]),
+                None => not_found(),
+            }
+        }
+        (&Method::GET, ["v1", "catalog", _, ty, id, relationship]) => {
+            match state.catalog.get(&key(ty, id)) {
+                Some(resource) => {
+                    relationship_page(request, &resource["relationships"][*relationship])
+                }
+                None => not_found(),
+            }
+        }
+        (&Method::GET, ["v1", "me", "storefront"]) => found(Some(
+            state
+                .catalog
+                .get(&key("storefronts", "us"))
+                .cloned()
+                .unwrap_or_else(default_storefront),
+        )),
+        (&Method::POST, ["v1", "me", "library"]) => library_add(state, request),
+        (&Method::GET, ["v1", "me", "library", "search"]) => search(request, |ty| {
+            state.library.get(ty).cloned().unwrap_or_default()
+        }),
+        (&Method::GET, ["v1", "me", "library", ty]) => {
+            library_many(state, request, &format!("library-{ty}"))
+        }
+        (&Method::POST, ["v1", "me", "library", "playlists"]) => playlist_create(state, request),
+        (&Method::GET, ["v1", "me", "library", ty, id]) => {
+            found(library_get(state, &format!("library-{ty}"), id).cloned())
+        }
+        (&Method::POST, ["v1", "me", "library", "playlists", id, "tracks"]) => {
+            playlist_add_tracks(state, request, id)
+        }
+        (&Method::GET, ["v1", "me", "library", ty, id, relationship]) => {
+            match library_get(state, &format!("library-{ty}"), id) {
+                Some(resource) => {
+                    relationship_page(request, &resource["relationships"][*relationship])
+                }
+                None => not_found(),
+            }
+        }
+        (&Method::GET, ["v1", "me", "recent", "played"]) => {
+            let resources = recently_played(state, |ty| !TRACK_TYPES.contains(&ty));
+            (StatusCode::OK, Some(page(request, &resources)))
+        }
+        (&Method::GET, ["v1", "me", "recent", "played", "tracks"]) => {
+            let types = request.ids("types").unwrap_or(TRACK_TYPES.to_vec());
+            let resources = recently_played(state, |ty| types.contains(&ty));
+            (StatusCode::OK, Some(page(request, &resources)))
+        }
+        (&Method::GET, ["v1", "me", "recent", "radio-stations"]) => {
+            let resources = recently_played(state, |ty| ty == "stations");
+            (StatusCode::OK, Some(page(request, &resources)))
+        }
+        (&Method::GET, ["v1", "me", "history", "heavy-rotation"]) => {
+            (StatusCode::OK, Some(page(request, &heavy_rotation(state))))
+        }
+        (&Method::GET, ["v1", "me", "recommendations"]) => {
+            catalog_many(state, request, "personal-recommendation")
+        }
+        (&Method::GET, ["v1", "me", "recommendations", id]) => found(
+            state
+                .catalog
+                .get(&key("personal-recommendation", id))
+                .cloned(),
+        ),
+        (&Method::GET, ["v1", "me", "ratings", ty]) => {
+            let Some(ids) = request.ids("ids") else {
+                return missing_parameter("ids");
+            };
+            let data = ids
+                .into_iter()
+                .filter_map(|id| state.ratings.get(&key(ty, id)).cloned())
+                .collect::<Vec<_>>();
+            (StatusCode::OK, Some(json!({ "data": data })))
+        }
+        (&Method::GET, ["v1", "me", "ratings", ty, id]) => {
+            found(state.ratings.get(&key(ty, id)).cloned())
+        }
+        (&Method::PUT, ["v1", "me", "ratings", ty, id]) => {
+            let value = request
+                .body
+                .as_ref()
+                .and_then(|e| e["attributes"]["value"].as_i64())
+                .unwrap_or(1);
+            let rating = json!({
+                "id": id,
+                "type": "ratings",
+                "href": format!("/v1/me/ratings/{ty}/{id}"),
+                "attributes": { "value": value },
+                "relationships": {}
+            });
+            state.ratings.insert(key(ty, id), rating.clone());
+            (StatusCode::OK, Some(json!({ "data": [rating] })))
+        }
+        (&Method::DELETE, ["v1", "me", "ratings", ty, id]) => {
+            match state.ratings.remove(&key(ty, id)) {
+                Some(_) => (StatusCode::NO_CONTENT, None),
+                None => not_found(),
+            }
+        }
+        _ => not_found(),
+    }
+}
+
+fn catalog_many(state: &MockState, request: &MockRequest, ty: &str) -> MockResponse {
+    // isrc filters report the resources each isrc matched in the meta, like the api does
+    if let Some(isrcs) = request.ids("filter[isrc]") {
+        let mut data = Vec::new();
+        let mut filters = serde_json::Map::new();
+        for isrc in isrcs {
+            let matches = state
+                .catalog
+                .values()
+                .filter(|e| e["type"] == ty && e["attributes"]["isrc"] == isrc)
+                .collect::<Vec<_>>();
+
+            let headers = matches
+                .iter()
+                .map(|e| json!({ "id": e["id"], "type": e["type"], "href": e["href"] }))
+                .collect::<Vec<_>>();
+            filters.insert(isrc.to_string(), Value::from(headers));
+
+            for resource in matches {
+                if !data.contains(resource) {
+                    data.push(resource.clone());
+                }
+            }
+        }
+
+        return (
+            StatusCode::OK,
+            Some(json!({ "data": data, "meta": { "filters": { "isrc": filters } } })),
+        );
+    }
+
+    // without ids the seeded resources of the type are listed, like the storefront and chart listings
+    let Some(ids) = request.ids("ids") else {
+        let mut resources = catalog_resources(state, ty);
+        if ty == "storefronts" && resources.is_empty() {
+            resources.push(default_storefront());
+        }
+        return (StatusCode::OK, Some(page(request, &resources)));
+    };
+
+    let data = ids
+        .into_iter()
+        .filter_map(|id| state.catalog.get(&key(ty, id)).cloned())
+        .collect::<Vec<_>>();
+    (StatusCode::OK, Some(json!({ "data": data })))
+}
+
+/// Get the seeded catalog resources of a type, ordered by id
+fn catalog_resources(state: &MockState, ty: &str) -> Vec<Value> {
+    let mut resources = state
+        .catalog
+        .iter()
+        .filter(|((e, _), _)| e == ty)
+        .collect::<Vec<_>>();
+    resources.sort_by_key(|(key, _)| *key);
+    resources.into_iter().map(|(_, e)| e.clone()).collect()
+}
+
+/// Get a library resource, `ty` being one of the `library-*` types
+fn library_get<'a>(state: &'a MockState, ty: &str, id: &str) -> Option<&'a Value> {
+    state.library.get(ty)?.iter().find(|e| e["id"] == id)
+}
+
+fn library_many(state: &MockState, request: &MockRequest, ty: &str) -> MockResponse {
+    let resources = state.library.get(ty).cloned().unwrap_or_default();
+
+    match request.ids("ids") {
+        Some(ids) => {
+            let data = ids
+                .into_iter()
+                .filter_map(|id| resources.iter().find(|e| e["id"] == id).cloned())
+                .collect::<Vec<_>>();
+            (StatusCode::OK, Some(json!({ "data": data })))
+        }
+        None => (StatusCode::OK, Some(page(request, &resources))),
+    }
+}
+
+fn library_add(state: &mut MockState, request: &MockRequest) -> MockResponse {
+    for ty in LIBRARY_TYPES {
+        let Some(ids) = request.ids(&format!("ids[{ty}]")) else {
+            continue;
+        };
+
+        for id in ids {
+            let Some(resource) = state.catalog.get(&key(ty, id)).cloned() else {
+                return not_found();
+            };
+
+            let library_type = format!("library-{ty}");
+            let library = state.library.entry(library_type.clone()).or_default();
+            let catalog_id = Value::from(id);
+            if library
+                .iter()
+                .any(|e| e["attributes"]["playParams"]["catalogId"] == catalog_id)
+            {
+                continue;
+            }
+
+            state.next_id += 1;
+            let library_id = format!("i.{}", state.next_id);
+            let mut attributes = resource["attributes"].clone();
+            if attributes.is_object() {
+                attributes["playParams"] = json!({
+                    "id": library_id,
+                    "kind": ty.trim_end_matches('s'),
+                    "isLibrary": true,
+                    "catalogId": id
+                });
+            }
+
+            library.push(json!({
+                "id": library_id,
+                "type": library_type,
+                "href": format!("/v1/me/library/{ty}/{library_id}"),
+                "attributes": attributes,
+                "relationships": {
+                    "catalog": { "href": format!("/v1/me/library/{ty}/{library_id}/catalog"), "data": [resource] }
+                }
+            }));
+        }
+    }
+
+    (StatusCode::ACCEPTED, Some(json!({ "data": [] })))
+}
+
+fn playlist_create(state: &mut MockState, request: &MockRequest) -> MockResponse {
+    let Some(body) = &request.body else {
+        return missing_parameter("body");
+    };
+    let Some(name) = body["attributes"]["name"].as_str() else {
+        return missing_parameter("attributes.name");
+    };
+
+    state.next_id += 1;
+    let id = format!("p.{}", state.next_id);
+    let tracks = resolve_tracks(state, &body["relationships"]["tracks"]["data"]);
+    let mut attributes = json!({
+        "name": name,
+        "canEdit": true,
+        "isPublic": body["attributes"]["isPublic"].as_bool().unwrap_or_default(),
+        "hasCatalog": false,
+        "playParams": { "id": id, "kind": "playlist", "isLibrary": true }
+    });
+    if let Some(description) = body["attributes"]["description"].as_str() {
+        attributes["description"] = json!({ "standard": description });
+    }
+
+    let playlist = json!({
+        "id": id,
+        "type": "library-playlists",
+        "href": format!("/v1/me/library/playlists/{id}"),
+        "attributes": attributes,
+        "relationships": {
+            "tracks": { "href": format!("/v1/me/library/playlists/{id}/tracks"), "data": tracks }
+        }
+    });
+
+    state
+        .library
+        .entry(String::from("library-playlists"))
+        .or_default()
+        .push(playlist.clone());
+
+    (StatusCode::CREATED, Some(json!({ "data": [playlist] })))
+}
+
+fn playlist_add_tracks(state: &mut MockState, request: &MockRequest, id: &str) -> MockResponse {
+    let Some(body) = &request.body else {
+        return missing_parameter("body");
+    };
+    let tracks = resolve_tracks(state, &body["data"]);
+
+    let Some(playlist) = state
+        .library
+        .get_mut("library-playlists")
+        .and_then(|e| e.iter_mut().find(|e| e["id"] == id))
+    else {
+        return not_found();
+    };
+
+    let relationship = &mut playlist["relationships"]["tracks"]["data"];
+    if !relationship.is_array() {
+        *relationship = json!([]);
+    }
+    if let Some(data) = relationship.as_array_mut() {
+        data.extend(tracks);
+    }
+
+    (StatusCode::NO_CONTENT, None)
+}
+
+/// Replace thin track references with the seeded resources when they exist
+fn resolve_tracks(state: &MockState, tracks: &Value) -> Vec<Value> {
+    tracks
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|track| {
+            let (ty, id) = resource_key(track);
+            let resource = match ty.strip_prefix("library-") {
+                Some(_) => library_get(state, &ty, &id),
+                None => state.catalog.get(&(ty, id)),
+            };
+            resource.unwrap_or(track).clone()
+        })
+        .collect()
+}
+
+/// Search the resources of the requested `types` by name, `resources` listing the resources of a type
+fn search(request: &MockRequest, resources: impl Fn(&str) -> Vec<Value>) -> MockResponse {
+    let Some(types) = request.ids("types") else {
+        return missing_parameter("types");
+    };
+    let Some(term) = request.query_value("term") else {
+        return missing_parameter("term");
+    };
+    let term = term.replace('+', " ").to_lowercase();
+
+    let mut results = serde_json::Map::new();
+    for ty in types {
+        let matches = resources(ty)
+            .into_iter()
+            .filter(|e| {
+                e["attributes"]["name"]
+                    .as_str()
+                    .is_some_and(|name| name.to_lowercase().contains(&term))
+            })
+            .collect::<Vec<_>>();
+
+        if !matches.is_empty() {
+            results.insert(ty.to_string(), page(request, &matches));
+        }
+    }
+
+    (StatusCode::OK, Some(json!({ "results": results })))
+}
+
+/// Get the played resources matching `filter`, most recently played first
+fn recently_played(state: &MockState, filter: impl Fn(&str) -> bool) -> Vec<Value> {
+    let mut resources = Vec::<Value>::new();
+    for resource in state.played.iter().rev() {
+        let key = resource_key(resource);
+        if filter(&key.0) && !resources.iter().any(|e| resource_key(e) == key) {
+            resources.push(resource.clone());
+        }
+    }
+
+    resources
+}
+
+/// Get the played containers, most played first
+fn heavy_rotation(state: &MockState) -> Vec<Value> {
+    let plays = |resource: &Value| {
+        let key = resource_key(resource);
+        state
+            .played
+            .iter()
+            .filter(|e| resource_key(e) == key)
+            .count()
+    };
+
+    let mut resources = recently_played(state, |ty| !TRACK_TYPES.contains(&ty));
+    resources.sort_by_key(|e| std::cmp::Reverse(plays(e)));
+    resources
+}
+
+fn relationship_page(request: &MockRequest, relationship: &Value) -> MockResponse {
+    let data = relationship["data"].as_array().cloned().unwrap_or_default();
+    (StatusCode::OK, Some(page(request, &data)))
+}
+
+fn view(request: &MockRequest, view: &Value) -> MockResponse {
+    if view.is_null() {
+        return not_found();
+    }
+
+    let data = view["data"].as_array().cloned().unwrap_or_default();
+    let mut page = page(request, &data);
+    page["attributes"] = view["attributes"].clone();
+    (StatusCode::OK, Some(page))
+}
+
+/// Paginate resources using the `offset` and `limit` query parameters
+fn page(request: &MockRequest, resources: &[Value]) -> Value {
+    let offset = request
+        .query_value("offset")
+        .and_then(|e| e.parse::<usize>().ok())
+        .unwrap_or_default();
+    let limit = request
+        .query_value("limit")
+        .and_then(|e| e.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_LIMIT)
+        .max(1);
+
+    let data = resources
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut page = json!({
+        "href": request.path,
+        "data": data,
+        "meta": { "total": resources.len() }
+    });
+    if offset + limit < resources.len() {
+        page["next"] = json!(format!(
+            "{}?offset={}&limit={limit}",
+            request.path,
+            offset + limit
+        ));
+    }
+
+    page
+}
+
+fn found(resource: Option<Value>) -> MockResponse {
+    match resource {
+        Some(resource) => (StatusCode::OK, Some(json!({ "data": [resource] }))),
+        None => not_found(),
+    }
+}
+
+fn not_found() -> MockResponse {
+    error(StatusCode::NOT_FOUND, "40400", "Resource Not Found")
+}
+
+fn missing_parameter(parameter: &str) -> MockResponse {
+    let (status, mut body) = error(StatusCode::BAD_REQUEST, "40005", "Missing Parameter");
+    if let Some(body) = &mut body {
+        body["errors"][0]["detail"] = json!(format!("Missing parameter {parameter}"));
+    }
+    (status, body)
+}
+
+/// Apple shaped error response
+fn error(status: StatusCode, code: &str, title: &str) -> MockResponse {
+    let body = json!({
+        "errors": [{
+            "id": format!("{:016X}", fastrand::u64(..)),
+            "title": title,
+            "detail": title,
+            "status": status.as_u16().to_string(),
+            "code": code
+        }]
+    });
+
+    (status, Some(body))
+}
+
+fn default_storefront() -> Value {
+    json!({
+        "id": "us",
+        "type": "storefronts",
+        "href": "/v1/storefronts/us",
+        "attributes": {
+            "name": "United States",
+            "defaultLanguageTag": "en-US",
+            "supportedLanguageTags": ["en-US", "es-MX"],
+            "explicitContentPolicy": "allowed"
+        }
+    })
+}
+
+fn key(ty: &str, id: &str) -> (String, String) {
+    (ty.to_string(), id.to_string())
+}
+
+/// Percent decode a query component
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|e| std::str::from_utf8(e).ok())
+            .and_then(|e| u8::from_str_radix(e, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
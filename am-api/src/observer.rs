@@ -0,0 +1,273 @@
+//! Request observers and metrics
+
+use crate::error::Error;
+use crate::resource::ErrorResponse;
+use crate::transport::{endpoint_template, TransportRequest, TransportResponse};
+use reqwest::{Method, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Observer of the http calls sent by an [`ApiClient`](crate::ApiClient)
+///
+/// Observers are notified for every attempt sent through the transport,
+/// responses served from a cache are not observed
+pub trait Observer: Send + Sync {
+    /// Called before a request is sent
+    fn on_request(&self, event: &RequestEvent) {
+        let _ = event;
+    }
+
+    /// Called after a response was received or the transport failed
+    fn on_response(&self, event: &ResponseEvent) {
+        let _ = event;
+    }
+}
+
+impl<T: Observer + ?Sized> Observer for Arc<T> {
+    fn on_request(&self, event: &RequestEvent) {
+        self.as_ref().on_request(event)
+    }
+
+    fn on_response(&self, event: &ResponseEvent) {
+        self.as_ref().on_response(event)
+    }
+}
+
+/// Event emitted before a request is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestEvent {
+    /// Request method
+    pub method: Method,
+    /// Endpoint path
+    pub path: String,
+    /// Endpoint template, see [`endpoint_template`]
+    pub endpoint: String,
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// Request body size in bytes
+    pub request_bytes: usize,
+}
+
+/// Event emitted after a response was received or the transport failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseEvent {
+    /// Request method
+    pub method: Method,
+    /// Endpoint path
+    pub path: String,
+    /// Endpoint template, see [`endpoint_template`]
+    pub endpoint: String,
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// Request body size in bytes
+    pub request_bytes: usize,
+    /// Response status, [`None`] if the transport failed
+    pub status: Option<StatusCode>,
+    /// Response body size in bytes
+    pub response_bytes: usize,
+    /// Time between sending the request and receiving the response
+    pub latency: Duration,
+    /// Apple music error codes of an unsuccessful response
+    pub error_codes: Vec<String>,
+    /// Transport error message
+    pub transport_error: Option<String>,
+}
+
+impl RequestEvent {
+    pub(crate) fn new(request: &TransportRequest, base_url: &str, attempt: u32) -> RequestEvent {
+        let path = request.path(base_url);
+
+        RequestEvent {
+            method: request.method.clone(),
+            path: path.to_string(),
+            endpoint: endpoint_template(path),
+            attempt,
+            request_bytes: request.body.as_ref().map(Vec::len).unwrap_or_default(),
+        }
+    }
+}
+
+impl ResponseEvent {
+    pub(crate) fn new(
+        request: RequestEvent,
+        result: &Result<TransportResponse, Error>,
+        latency: Duration,
+    ) -> ResponseEvent {
+        let (status, response_bytes, error_codes, transport_error) = match result {
+            Ok(response) => {
+                let error_codes = match response.status.is_success() {
+                    true => Vec::new(),
+                    false => response
+                        .json::<ErrorResponse>()
+                        .map(|e| e.errors.into_iter().map(|e| e.code).collect())
+                        .unwrap_or_default(),
+                };
+                (
+                    Some(response.status),
+                    response.body.len(),
+                    error_codes,
+                    None,
+                )
+            }
+            Err(e) => (None, 0, Vec::new(), Some(e.to_string())),
+        };
+
+        ResponseEvent {
+            method: request.method,
+            path: request.path,
+            endpoint: request.endpoint,
+            attempt: request.attempt,
+            request_bytes: request.request_bytes,
+            status,
+            response_bytes,
+            latency,
+            error_codes,
+            transport_error,
+        }
+    }
+
+    /// Check if this response is an error
+    pub fn is_error(&self) -> bool {
+        !self
+            .status
+            .is_some_and(|e| e.is_success() || e.is_redirection())
+    }
+}
+
+/// Upper bounds of the latency histogram buckets in milliseconds
+pub const LATENCY_BUCKETS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Upper bounds of the response size histogram buckets in bytes
+pub const BYTES_BUCKETS: [u64; 8] = [
+    1 << 10,
+    4 << 10,
+    16 << 10,
+    64 << 10,
+    256 << 10,
+    1 << 20,
+    4 << 20,
+    16 << 20,
+];
+
+/// Histogram with fixed buckets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Inclusive upper bounds of the buckets, values above the last bound are counted in an overflow bucket
+    pub bounds: Vec<u64>,
+    /// Amount of values in each bucket, one more than [`Histogram::bounds`]
+    pub counts: Vec<u64>,
+    /// Sum of all values
+    pub sum: u64,
+    /// Amount of values
+    pub count: u64,
+}
+
+impl Histogram {
+    /// Create a new empty [`Histogram`] instance
+    pub fn new(bounds: &[u64]) -> Histogram {
+        Histogram {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len() + 1],
+            sum: 0,
+            count: 0,
+        }
+    }
+
+    /// Record a value
+    pub fn record(&mut self, value: u64) {
+        let bucket = self.bounds.partition_point(|bound| *bound < value);
+        self.counts[bucket] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Metrics of a single endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointMetrics {
+    /// Amount of requests
+    pub requests: u64,
+    /// Amount of failed requests
+    pub errors: u64,
+    /// Amount of failed requests by apple music error code
+    pub errors_by_code: HashMap<String, u64>,
+    /// Sent request body bytes
+    pub request_bytes: u64,
+    /// Response size histogram in bytes
+    pub response_bytes: Histogram,
+    /// Latency histogram in milliseconds
+    pub latency: Histogram,
+}
+
+impl Default for EndpointMetrics {
+    fn default() -> Self {
+        EndpointMetrics {
+            requests: 0,
+            errors: 0,
+            errors_by_code: HashMap::new(),
+            request_bytes: 0,
+            response_bytes: Histogram::new(&BYTES_BUCKETS),
+            latency: Histogram::new(&LATENCY_BUCKETS),
+        }
+    }
+}
+
+/// [`Observer`] aggregating request metrics in memory, keyed by method and endpoint template
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// let metrics = Arc::new(MetricsAggregator::new());
+/// client.add_observer(metrics.clone());
+///
+/// for ((method, endpoint), metrics) in metrics.snapshot() {
+///     println!("{method} {endpoint}: {} requests", metrics.requests);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MetricsAggregator {
+    endpoints: Mutex<HashMap<(Method, String), EndpointMetrics>>,
+}
+
+impl MetricsAggregator {
+    /// Create a new [`MetricsAggregator`] instance
+    pub fn new() -> MetricsAggregator {
+        MetricsAggregator::default()
+    }
+
+    /// Get a snapshot of the current metrics
+    pub fn snapshot(&self) -> HashMap<(Method, String), EndpointMetrics> {
+        self.lock().clone()
+    }
+
+    /// Remove all recorded metrics
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(Method, String), EndpointMetrics>> {
+        self.endpoints.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Observer for MetricsAggregator {
+    fn on_response(&self, event: &ResponseEvent) {
+        let mut endpoints = self.lock();
+        let metrics = endpoints
+            .entry((event.method.clone(), event.endpoint.clone()))
+            .or_default();
+
+        metrics.requests += 1;
+        metrics.request_bytes += event.request_bytes as u64;
+        metrics.response_bytes.record(event.response_bytes as u64);
+        metrics.latency.record(event.latency.as_millis() as u64);
+
+        if event.is_error() {
+            metrics.errors += 1;
+            for code in &event.error_codes {
+                *metrics.errors_by_code.entry(code.clone()).or_default() += 1;
+            }
+        }
+    }
+}
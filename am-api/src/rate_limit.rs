@@ -0,0 +1,155 @@
+//! Client-side rate limiting
+
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Route kind, rate limits are tracked separately for each kind
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RouteKind {
+    /// Catalog and other non-personalized routes
+    Catalog,
+    /// Personalized routes under `/v1/me`
+    Personalized,
+}
+
+impl RouteKind {
+    /// Get route kind for an endpoint path
+    pub fn from_path(path: &str) -> RouteKind {
+        match path == "/v1/me" || path.starts_with("/v1/me/") {
+            true => RouteKind::Personalized,
+            false => RouteKind::Catalog,
+        }
+    }
+}
+
+/// Token bucket rate limit
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RateLimit {
+    /// Maximum amount of requests that can be sent in a burst
+    pub burst: u32,
+    /// Amount of requests that are allowed per period
+    pub requests: u32,
+    /// Refill period
+    pub period: Duration,
+}
+
+impl RateLimit {
+    /// Create a new [`RateLimit`] instance allowing `requests` per `period`, with a burst of the same size
+    pub fn new(requests: u32, period: Duration) -> RateLimit {
+        RateLimit {
+            burst: requests,
+            requests,
+            period,
+        }
+    }
+
+    /// Create a new [`RateLimit`] instance allowing `requests` per second
+    pub fn per_second(requests: u32) -> RateLimit {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Set the burst size
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Rate limiter shared by all clones of an [`ApiClient`](crate::ApiClient)
+///
+/// Requests wait for capacity instead of failing when a budget is exhausted
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    catalog: Option<TokenBucket>,
+    personalized: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    /// Create a new [`RateLimiter`] instance without any limits
+    pub fn new() -> RateLimiter {
+        RateLimiter::default()
+    }
+
+    /// Limit catalog and other non-personalized routes
+    pub fn catalog(mut self, limit: RateLimit) -> Self {
+        self.catalog = Some(TokenBucket::new(limit));
+        self
+    }
+
+    /// Limit personalized routes under `/v1/me`
+    pub fn personalized(mut self, limit: RateLimit) -> Self {
+        self.personalized = Some(TokenBucket::new(limit));
+        self
+    }
+
+    /// Wait until a request to a route of this kind can be sent
+    pub async fn acquire(&self, route: RouteKind) {
+        let bucket = match route {
+            RouteKind::Catalog => self.catalog.as_ref(),
+            RouteKind::Personalized => self.personalized.as_ref(),
+        };
+
+        if let Some(bucket) = bucket {
+            bucket.acquire().await;
+        }
+    }
+}
+
+/// Token bucket
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> TokenBucket {
+        TokenBucket {
+            limit,
+            state: Mutex::new(TokenBucketState {
+                tokens: limit.burst.max(1) as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Bucket size, at least one token so requests can always be sent eventually
+    fn burst(&self) -> f64 {
+        self.limit.burst.max(1) as f64
+    }
+
+    /// Tokens refilled per second
+    fn rate(&self) -> f64 {
+        self.limit.requests as f64 / self.limit.period.as_secs_f64()
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.rate()).min(self.burst());
+                state.last_refill = now;
+
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::try_from_secs_f64((1.0 - state.tokens) / self.rate())
+                    .unwrap_or(self.limit.period)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Chunked multi-id requests
+
+use crate::error::{ApiError, Error};
+use crate::request::context::{ContextContainer, RequestContext};
+use crate::resource::{MusicError, ResourceInfo, ResponseMeta};
+use crate::ApiClient;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::pin::pin;
+use std::sync::Arc;
+
+/// Default maximum amount of concurrent requests sent for a multi-id fetch
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Result of a multi-id fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManyResponse<R> {
+    /// Resources that were found, in the order of the requested ids
+    pub data: Vec<R>,
+    /// Requested ids that did not resolve to a resource
+    pub unresolved: Vec<Unresolved>,
+    /// Errors reported by the api
+    pub errors: Vec<MusicError>,
+    /// Set when the api answered `304 Not Modified` for every chunk and the data is unchanged since the previous response
+    pub not_modified: bool,
+}
+
+/// Requested id that did not resolve to a resource
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Unresolved {
+    /// Requested id
+    pub id: String,
+    /// Error reported by the api for this id, if any
+    pub error: Option<MusicError>,
+}
+
+impl<R> ManyResponse<R> {
+    /// Check if every requested id resolved to a resource
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+
+    /// Get the ids that did not resolve to a resource
+    pub fn unresolved_ids(&self) -> Vec<&str> {
+        self.unresolved.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    fn extend(&mut self, other: ManyResponse<R>) {
+        self.data.extend(other.data);
+        self.unresolved.extend(other.unresolved);
+        self.errors.extend(other.errors);
+        self.not_modified &= other.not_modified;
+    }
+}
+
+impl<R> Default for ManyResponse<R> {
+    fn default() -> Self {
+        ManyResponse {
+            data: Vec::new(),
+            unresolved: Vec::new(),
+            errors: Vec::new(),
+            not_modified: false,
+        }
+    }
+}
+
+impl<R> IntoIterator for ManyResponse<R> {
+    type Item = R;
+    type IntoIter = std::vec::IntoIter<R>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+/// Response of a single chunk
+#[derive(Deserialize)]
+#[serde(bound = "R: DeserializeOwned")]
+struct ChunkResponse<R> {
+    #[serde(default = "Vec::default")]
+    data: Vec<R>,
+    #[serde(default)]
+    errors: Vec<MusicError>,
+    #[serde(default)]
+    meta: ResponseMeta,
+}
+
+/// Fetch resources by id, splitting the ids into chunks of at most `chunk_size`
+///
+/// Chunks are fetched with at most `concurrency` requests in flight and yielded in input order,
+/// resources of a chunk are sorted by the position of the id they resolve.
+/// Ids missing from a chunk response, or of a chunk the api rejected because of its ids, are reported as [`Unresolved`].
+/// Any other unsuccessful response fails the stream
+pub(crate) fn fetch_many<'a, R>(
+    client: &'a ApiClient,
+    endpoint: String,
+    request_context: RequestContext,
+    id_query: &'static str,
+    ids: &'a [&'a str],
+    chunk_size: usize,
+    concurrency: usize,
+) -> impl Stream<Item = Result<ManyResponse<R>, Error>> + 'a
+where
+    R: ContextContainer + DeserializeOwned + ResourceInfo + 'a,
+{
+    stream::iter(ids.chunks(chunk_size.max(1)))
+        .map(move |chunk| {
+            let endpoint = endpoint.clone();
+            let mut request_context = request_context.clone();
+            request_context
+                .query
+                .push((id_query.to_string(), chunk.join(",")));
+
+            async move {
+                let request_context = Arc::new(request_context);
+
+                let response = client
+                    .get(&endpoint)
+                    .context(&request_context)
+                    .send()
+                    .await?
+                    .error_for_status();
+
+                let (response, not_modified) = match response {
+                    Ok(response) => (response.json::<ChunkResponse<R>>()?, response.not_modified),
+                    Err(Error::NotFound(error) | Error::Unexpected(error))
+                        if rejects_ids(&error, id_query) =>
+                    {
+                        let errors = error.response.map(|e| e.errors).unwrap_or_default();
+                        return Ok(rejected_chunk(chunk, errors));
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let mut response = resolve_chunk(chunk, id_query, response, request_context);
+                response.not_modified = not_modified;
+                Ok(response)
+            }
+        })
+        .buffered(concurrency.max(1))
+}
+
+/// Flatten chunk responses into a stream of resources, dropping unresolved ids
+pub(crate) fn flatten_many<'a, R: 'a>(
+    chunks: impl Stream<Item = Result<ManyResponse<R>, Error>> + 'a,
+) -> impl Stream<Item = Result<R, Error>> + 'a {
+    chunks
+        .map_ok(|chunk| stream::iter(chunk.data.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+/// Merge chunk responses into a single [`ManyResponse`]
+pub(crate) async fn collect_many<R>(
+    chunks: impl Stream<Item = Result<ManyResponse<R>, Error>>,
+) -> Result<ManyResponse<R>, Error> {
+    let mut chunks = pin!(chunks);
+
+    let Some(mut response) = chunks.try_next().await? else {
+        return Ok(ManyResponse::default());
+    };
+    while let Some(chunk) = chunks.try_next().await? {
+        response.extend(chunk);
+    }
+
+    Ok(response)
+}
+
+fn resolve_chunk<R>(
+    chunk: &[&str],
+    id_query: &str,
+    mut response: ChunkResponse<R>,
+    request_context: Arc<RequestContext>,
+) -> ManyResponse<R>
+where
+    R: ContextContainer + ResourceInfo,
+{
+    response.data.set_context(request_context);
+
+    // resource ids are case-sensitive, only filter values such as isrcs are matched ignoring case
+    let filter = filter_name(id_query);
+    let requested_id = |id: &str| match filter {
+        Some(_) => id.to_lowercase(),
+        None => id.to_string(),
+    };
+
+    let mut positions = HashMap::new();
+    for (position, id) in chunk.iter().enumerate() {
+        positions.entry(requested_id(id)).or_insert(position);
+    }
+
+    // filter queries report their matches in the meta, plain ids are matched against the resources.
+    // both map a resource id to the position of the first requested id it resolves
+    let mut resolved = HashSet::new();
+    let mut resource_positions = HashMap::new();
+    match filter {
+        Some(filter) => {
+            let matches = response.meta.filters.remove(filter).unwrap_or_default();
+            for (id, matches) in matches {
+                let Some(&position) = positions.get(&requested_id(&id)) else {
+                    continue;
+                };
+                if !matches.is_empty() {
+                    resolved.insert(position);
+                }
+                for header in matches {
+                    let resource_position = resource_positions.entry(header.id).or_insert(position);
+                    *resource_position = position.min(*resource_position);
+                }
+            }
+        }
+        None => {
+            for resource in &response.data {
+                let id = &resource.get_header().id;
+                if let Some(&position) = positions.get(id) {
+                    resolved.insert(position);
+                    resource_positions.insert(id.clone(), position);
+                }
+            }
+        }
+    }
+
+    // keep the order of the requested ids, resources matching none of them go last
+    response.data.sort_by_key(|e| {
+        resource_positions
+            .get(&e.get_header().id)
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+
+    let unresolved = chunk
+        .iter()
+        .filter(|id| !resolved.contains(&positions[&requested_id(id)]))
+        .map(|id| Unresolved {
+            id: id.to_string(),
+            error: error_for(id, &response.errors),
+        })
+        .collect();
+
+    ManyResponse {
+        data: response.data,
+        unresolved,
+        errors: response.errors,
+        not_modified: false,
+    }
+}
+
+fn rejected_chunk<R>(chunk: &[&str], errors: Vec<MusicError>) -> ManyResponse<R> {
+    let unresolved = chunk
+        .iter()
+        .map(|id| Unresolved {
+            id: id.to_string(),
+            error: error_for(id, &errors).or_else(|| errors.first().cloned()),
+        })
+        .collect();
+
+    ManyResponse {
+        data: Vec::new(),
+        unresolved,
+        errors,
+        not_modified: false,
+    }
+}
+
+/// Check if every error of a rejected chunk is about the id query parameter
+fn rejects_ids(error: &ApiError, id_query: &str) -> bool {
+    let errors = error
+        .response
+        .as_ref()
+        .map(|e| e.errors.as_slice())
+        .unwrap_or_default();
+
+    !errors.is_empty()
+        && errors.iter().all(|e| {
+            e.source
+                .as_ref()
+                .and_then(|e| e.parameter.as_deref())
+                .is_some_and(|parameter| parameter == id_query)
+        })
+}
+
+/// Find the error mentioning an id
+fn error_for(id: &str, errors: &[MusicError]) -> Option<MusicError> {
+    errors
+        .iter()
+        .find(|e| mentions(&e.detail, id) || mentions(&e.title, id))
+        .cloned()
+}
+
+/// Check if a message contains an id as a whole word, so `1` doesn't match `12`
+fn mentions(message: &str, id: &str) -> bool {
+    message
+        .split(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | '-' | '_')))
+        .any(|word| word.trim_matches('.') == id)
+}
+
+/// Get the filter name of a `filter[...]` query
+fn filter_name(id_query: &str) -> Option<&str> {
+    id_query.strip_prefix("filter[")?.strip_suffix(']')
+}
@@ -1,4 +1,5 @@
 //! Request builder
+use crate::request::batch::DEFAULT_CONCURRENCY;
 use crate::request::context::RequestContext;
 use crate::request::extension::{ExtensionStorage, ExtensionTrait};
 use crate::request::relationship::{RelationshipStorage, RelationshipTrait};
@@ -18,6 +19,10 @@ pub struct MusicRequestBuilder<'a, BuilderType, Data = ()> {
     pub(crate) relationships: RelationshipStorage,
     /// Views
     pub(crate) views: ViewStorage,
+    /// Bypass the response cache
+    pub(crate) bypass_cache: bool,
+    /// Maximum amount of concurrent requests
+    pub(crate) concurrency: usize,
     /// Data
     pub(crate) data: Data,
     pub(crate) _marker: PhantomData<BuilderType>,
@@ -36,6 +41,22 @@ impl<'a, BuilderType, Data> MusicRequestBuilder<'a, BuilderType, Data> {
         self
     }
 
+    /// Bypass the client's response cache for this request
+    ///
+    /// The response is fetched from the api and is not stored in the cache
+    pub fn bypass_cache(mut self) -> Self {
+        self.bypass_cache = true;
+        self
+    }
+
+    /// Set the maximum amount of concurrent requests sent when fetching multiple resources by id
+    ///
+    /// Ids are split into chunks that fit in a single request, defaults to [`DEFAULT_CONCURRENCY`]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
     /// Extend resource attributes
     pub fn extend(mut self, extension: impl ExtensionTrait) -> Self {
         self.extensions.add_extension(extension);
@@ -77,6 +98,7 @@ impl<'a, BuilderType, Data> MusicRequestBuilder<'a, BuilderType, Data> {
         let mut context = RequestContext {
             storefront,
             query: Vec::from([(String::from("l"), localization.to_string())]),
+            bypass_cache: self.bypass_cache,
         };
 
         self.extensions.build_query_drain(&mut context);
@@ -98,6 +120,8 @@ where
             extensions: Default::default(),
             relationships: Default::default(),
             views: Default::default(),
+            bypass_cache: false,
+            concurrency: DEFAULT_CONCURRENCY,
             data: Default::default(),
             _marker: Default::default(),
         }
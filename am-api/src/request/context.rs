@@ -6,6 +6,7 @@ use std::sync::Arc;
 pub struct RequestContext {
     pub storefront: celes::Country,
     pub query: Vec<(String, String)>,
+    pub bypass_cache: bool,
 }
 
 /// Context container trait for filling out context in deserialized structs
@@ -13,6 +14,24 @@ pub trait ContextContainer {
     fn set_context(&mut self, context: Arc<RequestContext>);
 }
 
+/// Attach a request context to resources that were not fetched by a request builder
+///
+/// Resources deserialized from saved json have no context, relationships and views need one to fetch their next pages
+pub trait AttachContext {
+    /// Attach a context for `storefront` and `localization` to this resource and every resource nested inside of it
+    fn attach_context(&mut self, storefront: celes::Country, localization: &str);
+}
+
+impl<T: ContextContainer> AttachContext for T {
+    fn attach_context(&mut self, storefront: celes::Country, localization: &str) {
+        self.set_context(Arc::new(RequestContext {
+            storefront,
+            query: Vec::from([(String::from("l"), localization.to_string())]),
+            bypass_cache: false,
+        }));
+    }
+}
+
 impl<T: ContextContainer> ContextContainer for Option<T> {
     fn set_context(&mut self, context: Arc<RequestContext>) {
         if let Some(e) = self {
@@ -0,0 +1,243 @@
+//! Batched loading of resources by id
+
+use crate::error::Error;
+use crate::resource::catalog::activity::{Activity, ActivityGetRequestBuilder};
+use crate::resource::catalog::album::{Album, AlbumGetRequestBuilder};
+use crate::resource::catalog::artist::{Artist, ArtistGetRequestBuilder};
+use crate::resource::catalog::curator::{
+    AppleCurator, AppleCuratorGetRequestBuilder, Curator, CuratorGetRequestBuilder,
+};
+use crate::resource::catalog::music_video::{MusicVideo, MusicVideoGetRequestBuilder};
+use crate::resource::catalog::playlist::{Playlist, PlaylistGetRequestBuilder};
+use crate::resource::catalog::record_label::{RecordLabel, RecordLabelGetRequestBuilder};
+use crate::resource::catalog::song::{Song, SongGetRequestBuilder};
+use crate::resource::catalog::station::{
+    Station, StationGenre, StationGenreGetRequestBuilder, StationGetRequestBuilder,
+};
+use crate::resource::genre::{Genre, GenreGetRequestBuilder};
+use crate::resource::library::album::{LibraryAlbum, LibraryAlbumGetRequestBuilder};
+use crate::resource::library::artist::{LibraryArtist, LibraryArtistGetRequestBuilder};
+use crate::resource::library::music_video::{
+    LibraryMusicVideo, LibraryMusicVideoGetRequestBuilder,
+};
+use crate::resource::library::playlist::{LibraryPlaylist, LibraryPlaylistGetRequestBuilder};
+use crate::resource::library::song::{LibrarySong, LibrarySongGetRequestBuilder};
+use crate::ApiClient;
+use futures::future::BoxFuture;
+use futures::{FutureExt, TryFutureExt};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Default time a [`Loader`] waits for more ids before sending a batch
+pub const DEFAULT_WINDOW: Duration = Duration::from_millis(5);
+
+/// Request builder that can fetch multiple resources by id in one request, used by [`Loader`]
+pub trait BatchRequest: Send + 'static {
+    /// Resource type
+    type Resource: Clone + Send + 'static;
+
+    /// Override the storefront of this request
+    fn batch_storefront(self, storefront: celes::Country) -> Self;
+
+    /// Fetch multiple resources by id
+    fn batch_many<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> BoxFuture<'b, Result<Vec<Self::Resource>, Error>>;
+
+    /// Get the id of a resource
+    fn batch_id(resource: &Self::Resource) -> &str;
+}
+
+type BatchResult<R> = Result<Option<R>, Arc<Error>>;
+
+type Batches<R> = HashMap<Option<&'static str>, Batch<R>>;
+
+struct Batch<R> {
+    storefront: Option<celes::Country>,
+    waiters: Vec<(String, oneshot::Sender<BatchResult<R>>)>,
+}
+
+/// Loader collecting single resource lookups into batched multi-id requests
+///
+/// Lookups issued within a short window are sent as a single `many` request per storefront,
+/// built by the loader's builder function so extensions, includes and views are applied to every batch.
+/// Ids that are not returned by the api resolve to [`None`]
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// let loader = Loader::new(&client, || Album::get().include(AlbumRelationshipType::Tracks));
+/// let (a, b) = futures::join!(loader.load("1"), loader.load("2"));
+/// ```
+pub struct Loader<B: BatchRequest> {
+    client: ApiClient,
+    builder: Arc<dyn Fn() -> B + Send + Sync>,
+    window: Duration,
+    batches: Arc<Mutex<Batches<B::Resource>>>,
+}
+
+impl<B: BatchRequest> Loader<B> {
+    /// Create a new [`Loader`] instance, `builder` creates the request builder used for every batch
+    pub fn new(client: &ApiClient, builder: impl Fn() -> B + Send + Sync + 'static) -> Loader<B> {
+        Loader {
+            client: client.clone(),
+            builder: Arc::new(builder),
+            window: DEFAULT_WINDOW,
+            batches: Arc::default(),
+        }
+    }
+
+    /// Set the time to wait for more ids before sending a batch
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Load a resource by id
+    pub async fn load(&self, id: &str) -> Result<Option<B::Resource>, Error> {
+        self.load_with(None, id).await
+    }
+
+    /// Load a resource by id from a specific storefront
+    pub async fn load_from(
+        &self,
+        storefront: celes::Country,
+        id: &str,
+    ) -> Result<Option<B::Resource>, Error> {
+        self.load_with(Some(storefront), id).await
+    }
+
+    async fn load_with(
+        &self,
+        storefront: Option<celes::Country>,
+        id: &str,
+    ) -> Result<Option<B::Resource>, Error> {
+        let (sender, receiver) = oneshot::channel();
+        let key = storefront.map(|e| e.alpha2);
+
+        match self.lock().entry(key) {
+            Entry::Occupied(mut entry) => entry.get_mut().waiters.push((id.to_string(), sender)),
+            Entry::Vacant(entry) => {
+                entry.insert(Batch {
+                    storefront,
+                    waiters: Vec::from([(id.to_string(), sender)]),
+                });
+                tokio::spawn(self.clone().dispatch(key));
+            }
+        }
+
+        receiver
+            .await
+            .map_err(|_| Error::BatchCancelled)?
+            .map_err(|e| Arc::try_unwrap(e).unwrap_or_else(Error::Coalesced))
+    }
+
+    /// Wait for the batch window to pass and send the batch
+    async fn dispatch(self, key: Option<&'static str>) {
+        tokio::time::sleep(self.window).await;
+
+        let Some(batch) = self.lock().remove(&key) else {
+            return;
+        };
+
+        let mut seen = HashSet::new();
+        let ids = batch
+            .waiters
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .filter(|id| seen.insert(*id))
+            .collect::<Vec<_>>();
+
+        let mut builder = (self.builder)();
+        if let Some(storefront) = batch.storefront {
+            builder = builder.batch_storefront(storefront);
+        }
+
+        match builder.batch_many(&self.client, &ids).await {
+            Ok(resources) => {
+                let resources = resources
+                    .into_iter()
+                    .map(|e| (B::batch_id(&e).to_string(), e))
+                    .collect::<HashMap<_, _>>();
+
+                for (id, sender) in batch.waiters {
+                    let _ = sender.send(Ok(resources.get(&id).cloned()));
+                }
+            }
+            Err(e) => {
+                let e = Arc::new(e);
+                for (_, sender) in batch.waiters {
+                    let _ = sender.send(Err(e.clone()));
+                }
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Batches<B::Resource>> {
+        self.batches.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<B: BatchRequest> Clone for Loader<B> {
+    fn clone(&self) -> Self {
+        Loader {
+            client: self.client.clone(),
+            builder: self.builder.clone(),
+            window: self.window,
+            batches: self.batches.clone(),
+        }
+    }
+}
+
+macro_rules! batch_request_impl {
+    ($($builder:ident => $resource:ident: $method:ident $(, $arg:expr)?;)*) => {
+        $(
+            impl BatchRequest for $builder<'static> {
+                type Resource = $resource;
+
+                fn batch_storefront(self, storefront: celes::Country) -> Self {
+                    self.override_storefront(storefront)
+                }
+
+                fn batch_many<'b>(
+                    self,
+                    client: &'b ApiClient,
+                    ids: &'b [&'b str],
+                ) -> BoxFuture<'b, Result<Vec<Self::Resource>, Error>> {
+                    self.$method(client, ids $(, $arg)?)
+                        .map_ok(|response| response.data)
+                        .boxed()
+                }
+
+                fn batch_id(resource: &Self::Resource) -> &str {
+                    resource.header.id.as_str()
+                }
+            }
+        )*
+    };
+}
+
+batch_request_impl! {
+    ActivityGetRequestBuilder => Activity: many;
+    AlbumGetRequestBuilder => Album: many, false;
+    ArtistGetRequestBuilder => Artist: many;
+    AppleCuratorGetRequestBuilder => AppleCurator: main;
+    CuratorGetRequestBuilder => Curator: many;
+    MusicVideoGetRequestBuilder => MusicVideo: many, false;
+    PlaylistGetRequestBuilder => Playlist: many;
+    RecordLabelGetRequestBuilder => RecordLabel: many;
+    SongGetRequestBuilder => Song: many, false;
+    StationGetRequestBuilder => Station: many;
+    StationGenreGetRequestBuilder => StationGenre: many;
+    GenreGetRequestBuilder => Genre: many;
+    LibraryAlbumGetRequestBuilder => LibraryAlbum: many;
+    LibraryArtistGetRequestBuilder => LibraryArtist: many;
+    LibraryMusicVideoGetRequestBuilder => LibraryMusicVideo: many;
+    LibraryPlaylistGetRequestBuilder => LibraryPlaylist: many;
+    LibrarySongGetRequestBuilder => LibrarySong: many;
+}
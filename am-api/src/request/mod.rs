@@ -1,30 +1,32 @@
 //! Request builders and structures
 
 use crate::error::Error;
-use crate::resource::{ErrorResponse, ResourceResponse};
-use reqwest::Response;
+use crate::resource::ResourceResponse;
+use crate::transport::TransportResponse;
 use serde::de::DeserializeOwned;
 
+pub mod batch;
 pub mod builder;
 pub(crate) mod context;
 pub mod extension;
-pub(crate) mod paginated;
+pub mod loader;
+pub mod paginated;
 pub mod relationship;
 pub mod view;
 
+pub use context::AttachContext;
+
 /// Default fetch entries limit for a page
 pub const DEFAULT_FETCH_LIMIT: usize = 21;
 
-pub(crate) async fn try_resource_response<R>(
-    response: Response,
-) -> Result<ResourceResponse<R>, Error>
+/// Parse a [`TransportResponse`] into a [`ResourceResponse`], unsuccessful responses are returned as errors
+pub fn try_resource_response<R>(response: TransportResponse) -> Result<ResourceResponse<R>, Error>
 where
     R: DeserializeOwned,
 {
-    if !response.status().is_success() {
-        let error_response: ErrorResponse = response.json().await?;
-        return Err(Error::MusicError(error_response));
-    }
+    let response = response.error_for_status()?;
 
-    Ok(response.json().await?)
+    let mut resource_response: ResourceResponse<R> = response.json()?;
+    resource_response.not_modified = response.not_modified;
+    Ok(resource_response)
 }
@@ -3,47 +3,233 @@
 use crate::error::Error;
 use crate::request::context::{ContextContainer, RequestContext};
 use crate::request::try_resource_response;
+use crate::resource::ResourceResponse;
+use crate::trace::{self, Span};
 use crate::ApiClient;
 use async_stream::try_stream;
-use futures::Stream;
+use futures::{future, stream, Stream, StreamExt, TryStreamExt};
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-/// Paginate a request
-pub(crate) fn paginate<R>(
+/// Page of a paginated request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Page<T> {
+    /// Resources on this page
+    pub items: Vec<T>,
+    /// Total amount of resources, if reported by the api
+    pub total: Option<usize>,
+    /// Offset of the next page, [`None`] if the api reported this page as the last one
+    pub next_offset: Option<usize>,
+    /// Cursor of the next page, [`None`] if the api reported this page as the last one
+    pub cursor: Option<Cursor>,
+    /// Set when the api answered `304 Not Modified` and the page is unchanged since the previous response
+    pub not_modified: bool,
+}
+
+/// Position of the next page of a paginated request
+///
+/// Cursors can be serialized and passed to [`resume`] to continue a stream later,
+/// for example after a restart of the process
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Cursor {
+    /// Endpoint paginated by offset, or a `next` href reported by the api
+    href: String,
+    /// Offset of the next page, [`None`] if `href` is a `next` href
+    #[serde(default)]
+    offset: Option<usize>,
+    /// Storefront alpha2 code
+    storefront: String,
+    /// Query parameters sent with every page
+    #[serde(default)]
+    query: Vec<(String, String)>,
+    /// Bypass the response caches
+    #[serde(default)]
+    bypass_cache: bool,
+    /// Whether the api reported a `next` page before
+    #[serde(default)]
+    reports_next: bool,
+}
+
+impl Cursor {
+    fn new(href: String, offset: Option<usize>, request_context: &RequestContext) -> Cursor {
+        Cursor {
+            href,
+            offset,
+            storefront: request_context.storefront.alpha2.to_string(),
+            query: request_context.query.clone(),
+            bypass_cache: request_context.bypass_cache,
+            reports_next: false,
+        }
+    }
+
+    /// Create a cursor following a `next` href reported by the api
+    pub(crate) fn from_next(next: &str, request_context: &RequestContext) -> Cursor {
+        Cursor {
+            reports_next: true,
+            ..Cursor::new(next.to_string(), None, request_context)
+        }
+    }
+
+    /// Get the endpoint or `next` href of the next page
+    pub fn get_href(&self) -> &str {
+        self.href.as_str()
+    }
+
+    /// Get the offset of the next page, if known
+    pub fn get_offset(&self) -> Option<usize> {
+        self.offset.or_else(|| page_offset(&self.href))
+    }
+
+    fn request_context(&self) -> Result<RequestContext, Error> {
+        let storefront = celes::Country::from_alpha2(&self.storefront)
+            .map_err(|_| Error::UnknownStorefront(self.storefront.clone()))?;
+
+        Ok(RequestContext {
+            storefront,
+            query: self.query.clone(),
+            bypass_cache: self.bypass_cache,
+        })
+    }
+
+    /// Get the cursor of the page after a response to this cursor
+    ///
+    /// Once the api reported a `next` page, a missing `next` marks the last page.
+    /// Responses without either are followed until an empty page
+    fn next_page<R>(&self, response: &ResourceResponse<R>) -> Option<Cursor> {
+        let reports_next = self.reports_next || response.next.is_some();
+        let cursor = |href: &str, offset: Option<usize>| Cursor {
+            href: href.to_string(),
+            offset,
+            reports_next,
+            ..self.clone()
+        };
+
+        match (self.offset, response.next.as_deref()) {
+            (Some(offset), Some(next)) => match page_offset(next) {
+                Some(next_offset) => {
+                    (next_offset > offset).then(|| cursor(&self.href, Some(next_offset)))
+                }
+                None => Some(cursor(next, None)),
+            },
+            (Some(offset), None) => {
+                let next_offset = offset + response.data.len();
+                let has_next = match response.meta.total {
+                    Some(total) => next_offset < total,
+                    None => !reports_next,
+                };
+                has_next.then(|| cursor(&self.href, Some(next_offset)))
+            }
+            (None, Some(next)) => Some(cursor(next, None)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Get the offset query parameter of a page url
+fn page_offset(url: &str) -> Option<usize> {
+    url.split_once('?')?
+        .1
+        .split('&')
+        .find_map(|e| e.strip_prefix("offset="))?
+        .parse()
+        .ok()
+}
+
+/// Paginate a request page by page
+pub(crate) fn paginate_pages<R>(
     client: ApiClient,
     endpoint: String,
-    mut request_context: RequestContext,
-    mut offset: usize,
-) -> impl Stream<Item = Result<R, Error>>
+    request_context: RequestContext,
+    offset: usize,
+) -> impl Stream<Item = Result<Page<R>, Error>>
 where
     R: ContextContainer + DeserializeOwned,
 {
-    try_stream! {
-        loop {
-            request_context.query.push((String::from("offset"), offset.to_string()));
+    let span = trace::pagination_span(&endpoint);
+    let cursor = Cursor::new(endpoint, Some(offset), &request_context);
 
-            let response = client
-                .get(&endpoint)
-                .query(&request_context.query)
-                .send()
-                .await?;
+    follow(client, cursor, request_context, span)
+}
 
-            request_context.query.pop();
+/// Resume a paginated request from a [`Cursor`] of one of its pages
+///
+/// # Examples
+///
+/// ```no_run,ignore
+/// let cursor: Cursor = serde_json::from_str(&saved)?;
+/// let pages = resume::<LibrarySong>(&client, cursor);
+/// ```
+pub fn resume<R>(client: &ApiClient, cursor: Cursor) -> impl Stream<Item = Result<Page<R>, Error>>
+where
+    R: ContextContainer + DeserializeOwned,
+{
+    match cursor.request_context() {
+        Ok(request_context) => {
+            let span = trace::pagination_span(&cursor.href);
+            follow(client.clone(), cursor, request_context, span).left_stream()
+        }
+        Err(e) => stream::once(future::ready(Err(e))).right_stream(),
+    }
+}
 
-            let mut response = try_resource_response(response).await?;
-            response.data.set_context(Arc::new(request_context.clone()));
+/// Fetch pages starting at `cursor`
+pub(crate) fn follow<R>(
+    client: ApiClient,
+    cursor: Cursor,
+    request_context: RequestContext,
+    span: Span,
+) -> impl Stream<Item = Result<Page<R>, Error>>
+where
+    R: ContextContainer + DeserializeOwned,
+{
+    try_stream! {
+        let resource_context = Arc::new(request_context.clone());
+        let mut cursor = cursor;
+
+        loop {
+            let mut page_context = request_context.clone();
+            if let Some(offset) = cursor.offset {
+                page_context.query.push((String::from("offset"), offset.to_string()));
+            }
 
-            offset += response.data.len();
+            let page_span = trace::page_span(&span, cursor.get_offset());
+            let response = trace::instrument(
+                client.get(&cursor.href).context(&page_context).send(),
+                &page_span,
+            )
+            .await?;
+
+            let mut response = try_resource_response::<R>(response)?;
+            response.data.set_context(resource_context.clone());
 
             if response.data.is_empty() {
                 return;
             }
 
-            for resource in response.data {
-                yield resource;
-            }
+            let next = cursor.next_page(&response);
+            yield Page {
+                items: response.data,
+                total: response.meta.total,
+                next_offset: next.as_ref().and_then(Cursor::get_offset),
+                cursor: next.clone(),
+                not_modified: response.not_modified,
+            };
 
+            match next {
+                Some(next) => cursor = next,
+                None => return,
+            }
         }
     }
 }
+
+/// Flatten pages into a stream of resources
+pub(crate) fn flatten_pages<R>(
+    pages: impl Stream<Item = Result<Page<R>, Error>>,
+) -> impl Stream<Item = Result<R, Error>> {
+    pages
+        .map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+        .try_flatten()
+}
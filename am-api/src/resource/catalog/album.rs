@@ -2,6 +2,7 @@
 
 use crate::error::Error;
 use crate::primitive::{AudioVariant, ContentRating, EditorialNotes, PlayParameters};
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
@@ -15,13 +16,20 @@ use crate::resource::genre::Genre;
 use crate::resource::library::album::LibraryAlbum;
 use crate::resource::relationship::Relationship;
 use crate::resource::view::View;
-use crate::resource::{Resource, ResourceHeader};
+use crate::resource::{Resource, ResourceHeader, ResourceResponse};
 use crate::time::year_or_date::YearOrDate;
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of album ids per request
+const MAX_ALBUM_IDS: usize = 100;
+
+/// Maximum amount of album UPCs per request
+const MAX_ALBUM_UPCS: usize = 25;
+
 /// Album
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -172,55 +180,86 @@ pub type AlbumGetRequestBuilder<'a> = MusicRequestBuilder<'a, AlbumRequestBuilde
 
 impl<'a> AlbumGetRequestBuilder<'a> {
     /// Fetch one album by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Album>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Album>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one album by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Album>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
         let response = client
             .get(&format!(
                 "/v1/catalog/{storefront}/albums/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple albums by id
     ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    ///
     /// # Params
     ///
     /// * upc - if the ids are UPCs or album ids, false means album ids, true means UPCs
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
         upc: bool,
-    ) -> Result<Vec<Album>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-
-        let ids = ids.to_vec().join(",");
-        let id_query = match upc {
-            true => "filter[upc]",
-            false => "ids",
-        };
-        request_context.query.push((id_query.to_string(), ids));
+    ) -> Result<ManyResponse<Album>, Error> {
+        collect_many(self.many_chunks(client, ids, upc)).await
+    }
 
-        let request_context = Arc::new(request_context);
+    /// Fetch multiple albums by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+        upc: bool,
+    ) -> impl Stream<Item = Result<Album, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids, upc))
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/albums",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple albums by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+        upc: bool,
+    ) -> impl Stream<Item = Result<ManyResponse<Album>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let (id_query, chunk_size) = match upc {
+            true => ("filter[upc]", MAX_ALBUM_UPCS),
+            false => ("ids", MAX_ALBUM_IDS),
+        };
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/albums",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            id_query,
+            ids,
+            chunk_size,
+            self.concurrency,
+        )
     }
 }
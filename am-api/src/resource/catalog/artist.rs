@@ -2,6 +2,7 @@
 
 use crate::error::Error;
 use crate::primitive::EditorialNotes;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
@@ -15,12 +16,16 @@ use crate::resource::catalog::station::Station;
 use crate::resource::genre::Genre;
 use crate::resource::relationship::Relationship;
 use crate::resource::view::View;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of artist ids per request
+const MAX_ARTIST_IDS: usize = 25;
+
 /// Artist
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -181,7 +186,17 @@ pub type ArtistGetRequestBuilder<'a> = MusicRequestBuilder<'a, ArtistRequestBuil
 
 impl<'a> ArtistGetRequestBuilder<'a> {
     /// Fetch one artist by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Artist>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Artist>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one artist by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Artist>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -189,34 +204,58 @@ impl<'a> ArtistGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/artists/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple artists by id
-    pub async fn many(mut self, client: &ApiClient, ids: &[&str]) -> Result<Vec<Artist>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    pub async fn many(
+        self,
+        client: &ApiClient,
+        ids: &[&str],
+    ) -> Result<ManyResponse<Artist>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/artists",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple artists by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Artist, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple artists by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Artist>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/artists",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_ARTIST_IDS,
+            self.concurrency,
+        )
     }
 }
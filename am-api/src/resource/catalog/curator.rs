@@ -2,18 +2,26 @@
 
 use crate::error::Error;
 use crate::primitive::EditorialNotes;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
 use crate::resource::artwork::Artwork;
 use crate::resource::catalog::playlist::Playlist;
 use crate::resource::relationship::Relationship;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of apple curator ids per request
+const MAX_APPLE_CURATOR_IDS: usize = 25;
+
+/// Maximum amount of curator ids per request
+const MAX_CURATOR_IDS: usize = 25;
+
 /// Apple curator
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -93,11 +101,17 @@ pub type AppleCuratorGetRequestBuilder<'a> = MusicRequestBuilder<'a, AppleCurato
 
 impl<'a> AppleCuratorGetRequestBuilder<'a> {
     /// Fetch one apple curator by id
-    pub async fn one(
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<AppleCurator>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one apple curator by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
         mut self,
         client: &ApiClient,
         id: &str,
-    ) -> Result<Option<AppleCurator>, Error> {
+    ) -> Result<ResourceResponse<AppleCurator>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -105,39 +119,59 @@ impl<'a> AppleCuratorGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/apple-curators/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple apple curators by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn main(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<AppleCurator>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<AppleCurator>, Error> {
+        collect_many(self.main_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/apple-curators",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple apple curators by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`main`](Self::main) to report them
+    pub fn main_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<AppleCurator, Error>> + 'b {
+        flatten_many(self.main_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple apple curators by id in chunks
+    fn main_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<AppleCurator>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/apple-curators",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_APPLE_CURATOR_IDS,
+            self.concurrency,
+        )
     }
 }
 
@@ -202,7 +236,17 @@ pub type CuratorGetRequestBuilder<'a> = MusicRequestBuilder<'a, CuratorRequestBu
 
 impl<'a> CuratorGetRequestBuilder<'a> {
     /// Fetch one curator by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Curator>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Curator>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one curator by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Curator>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -210,34 +254,58 @@ impl<'a> CuratorGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/curators/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple curators by id
-    pub async fn many(mut self, client: &ApiClient, ids: &[&str]) -> Result<Vec<Curator>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    pub async fn many(
+        self,
+        client: &ApiClient,
+        ids: &[&str],
+    ) -> Result<ManyResponse<Curator>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/curators",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple curators by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Curator, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple curators by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Curator>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/curators",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_CURATOR_IDS,
+            self.concurrency,
+        )
     }
 }
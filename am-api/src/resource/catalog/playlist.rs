@@ -2,6 +2,7 @@
 
 use crate::error::Error;
 use crate::primitive::{PlayParameters, TrackType};
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
@@ -11,13 +12,17 @@ use crate::resource::catalog::artist::Artist;
 use crate::resource::library::playlist::LibraryPlaylist;
 use crate::resource::relationship::Relationship;
 use crate::resource::view::View;
-use crate::resource::{Resource, ResourceHeader};
+use crate::resource::{Resource, ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use time::OffsetDateTime;
 
+/// Maximum amount of playlist ids per request
+const MAX_PLAYLIST_IDS: usize = 25;
+
 /// Playlist
 #[derive(Context, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -156,7 +161,17 @@ pub type PlaylistGetRequestBuilder<'a> = MusicRequestBuilder<'a, PlaylistRequest
 
 impl<'a> PlaylistGetRequestBuilder<'a> {
     /// Fetch one playlist by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Playlist>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Playlist>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one playlist by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Playlist>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -164,35 +179,59 @@ impl<'a> PlaylistGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/playlists/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch many playlists by id
-    pub async fn many(mut self, client: &ApiClient, ids: &[&str]) -> Result<Vec<Playlist>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    pub async fn many(
+        self,
+        client: &ApiClient,
+        ids: &[&str],
+    ) -> Result<ManyResponse<Playlist>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/playlists",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch many playlists by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Playlist, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple resources by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Playlist>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/playlists",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_PLAYLIST_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch chart playlists by storefront value
@@ -213,11 +252,11 @@ impl<'a> PlaylistGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/playlists",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data)
     }
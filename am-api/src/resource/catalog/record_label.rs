@@ -1,6 +1,7 @@
 //! Record label
 
 use crate::error::Error;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
@@ -8,12 +9,16 @@ use crate::resource::artwork::Artwork;
 use crate::resource::attributes::{DescriptionAttribute, TitleOnlyAttribute};
 use crate::resource::catalog::album::Album;
 use crate::resource::view::View;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of record label ids per request
+const MAX_RECORD_LABEL_IDS: usize = 25;
+
 /// Record label
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -78,7 +83,17 @@ pub type RecordLabelGetRequestBuilder<'a> = MusicRequestBuilder<'a, RecordLabelR
 
 impl<'a> RecordLabelGetRequestBuilder<'a> {
     /// Fetch one record label by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<RecordLabel>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<RecordLabel>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one record label by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<RecordLabel>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -86,38 +101,58 @@ impl<'a> RecordLabelGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/record-labels/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch many record labels by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<RecordLabel>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<RecordLabel>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/record-labels",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch many record labels by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<RecordLabel, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple resources by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<RecordLabel>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/record-labels",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_RECORD_LABEL_IDS,
+            self.concurrency,
+        )
     }
 }
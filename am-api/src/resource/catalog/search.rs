@@ -13,7 +13,6 @@ use crate::resource::catalog::record_label::RecordLabel;
 use crate::resource::catalog::song::Song;
 use crate::resource::catalog::station::Station;
 use crate::resource::relationship::Relationship;
-use crate::resource::ErrorResponse;
 use crate::ApiClient;
 use am_api_proc_macro::Context;
 use serde::{Deserialize, Serialize};
@@ -72,18 +71,13 @@ impl<'a> CatalogSearchRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/search",
                 storefront = request_context.storefront.alpha2
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        let response = response.error_for_status()?;
 
-        let mut response = response
-            .json::<CatalogSearchResponse<CatalogSearchResults>>()
-            .await?;
+        let mut response = response.json::<CatalogSearchResponse<CatalogSearchResults>>()?;
         response.results.set_context(request_context);
         Ok(response.results)
     }
@@ -117,16 +111,13 @@ impl<'a> CatalogSearchRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/search/hints",
                 storefront = request_context.storefront.alpha2
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        let response = response.error_for_status()?;
 
-        let response: CatalogSearchResponse<CatalogSearchHints> = response.json().await?;
+        let response: CatalogSearchResponse<CatalogSearchHints> = response.json()?;
         Ok(response.results.terms)
     }
 
@@ -176,18 +167,13 @@ impl<'a> CatalogSearchRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/search/suggestions",
                 storefront = request_context.storefront.alpha2
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        let response = response.error_for_status()?;
 
-        let response = response
-            .json::<CatalogSearchResponse<CatalogSearchSuggestions>>()
-            .await?;
+        let response = response.json::<CatalogSearchResponse<CatalogSearchSuggestions>>()?;
         Ok(response.results.suggestions)
     }
 }
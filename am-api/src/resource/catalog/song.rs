@@ -2,6 +2,7 @@
 
 use crate::error::Error;
 use crate::primitive::{AudioVariant, ContentRating, EditorialNotes, PlayParameters, Preview};
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
@@ -13,13 +14,20 @@ use crate::resource::catalog::station::Station;
 use crate::resource::genre::Genre;
 use crate::resource::library::song::LibrarySong;
 use crate::resource::relationship::Relationship;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::time::year_or_date::YearOrDate;
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of song ids per request
+const MAX_SONG_IDS: usize = 300;
+
+/// Maximum amount of song ISRCs per request
+const MAX_SONG_ISRCS: usize = 25;
+
 /// Song
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -157,56 +165,86 @@ pub type SongGetRequestBuilder<'a> = MusicRequestBuilder<'a, SongRequestBuilder>
 
 impl<'a> SongGetRequestBuilder<'a> {
     /// Fetch one song by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Song>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Song>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one song by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Song>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
         let response = client
             .get(&format!(
                 "/v1/catalog/{storefront}/songs/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple songs by id
     ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    ///
     /// # Params
     ///
     /// * isrc - if the ids are ISRCs or song ids, false means song ids, true means ISRCs
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
         isrc: bool,
-    ) -> Result<Vec<Song>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-
-        let id_query = match isrc {
-            true => "filter[isrc]",
-            false => "ids",
-        };
-        request_context
-            .query
-            .push((id_query.to_string(), ids.to_vec().join(",")));
+    ) -> Result<ManyResponse<Song>, Error> {
+        collect_many(self.many_chunks(client, ids, isrc)).await
+    }
 
-        let request_context = Arc::new(request_context);
+    /// Fetch multiple songs by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+        isrc: bool,
+    ) -> impl Stream<Item = Result<Song, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids, isrc))
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/songs",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple songs by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+        isrc: bool,
+    ) -> impl Stream<Item = Result<ManyResponse<Song>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let (id_query, chunk_size) = match isrc {
+            true => ("filter[isrc]", MAX_SONG_ISRCS),
+            false => ("ids", MAX_SONG_IDS),
+        };
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/songs",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            id_query,
+            ids,
+            chunk_size,
+            self.concurrency,
+        )
     }
 }
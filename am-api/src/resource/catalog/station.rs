@@ -2,20 +2,27 @@
 
 use crate::error::Error;
 use crate::primitive::{ContentRating, EditorialNotes, PlayParameters};
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
 use crate::resource::artwork::Artwork;
 use crate::resource::catalog::curator::AppleCurator;
 use crate::resource::relationship::Relationship;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of station ids per request
+const MAX_STATION_IDS: usize = 25;
+
+/// Maximum amount of station genre ids per request
+const MAX_STATION_GENRE_IDS: usize = 25;
+
 /// Station
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -90,7 +97,17 @@ pub type StationGetRequestBuilder<'a> = MusicRequestBuilder<'a, StationRequestBu
 
 impl<'a> StationGetRequestBuilder<'a> {
     /// Fetch one station by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Station>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Station>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one station by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Station>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -98,35 +115,59 @@ impl<'a> StationGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/stations/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple stations by id
-    pub async fn many(mut self, client: &ApiClient, ids: &[&str]) -> Result<Vec<Station>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    pub async fn many(
+        self,
+        client: &ApiClient,
+        ids: &[&str],
+    ) -> Result<ManyResponse<Station>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/stations",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple stations by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Station, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple stations by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Station>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/stations",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_STATION_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch live radio stations
@@ -143,11 +184,11 @@ impl<'a> StationGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/stations",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data)
     }
@@ -165,11 +206,11 @@ impl<'a> StationGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/stations",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data.into_iter().next())
     }
@@ -240,11 +281,17 @@ pub type StationGenreGetRequestBuilder<'a> = MusicRequestBuilder<'a, StationGenr
 
 impl<'a> StationGenreGetRequestBuilder<'a> {
     /// Fetch one station genre by id
-    pub async fn one(
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<StationGenre>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one station genre by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
         mut self,
         client: &ApiClient,
         id: &str,
-    ) -> Result<Option<StationGenre>, Error> {
+    ) -> Result<ResourceResponse<StationGenre>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -252,39 +299,59 @@ impl<'a> StationGenreGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/station-genres/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple station genres by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<StationGenre>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<StationGenre>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/station-genres",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple station genres by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<StationGenre, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple station genres by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<StationGenre>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/station-genres",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_STATION_GENRE_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch all station genres
@@ -295,17 +362,33 @@ impl<'a> StationGenreGetRequestBuilder<'a> {
     ///
     /// * offset - query offset
     pub fn all(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<StationGenre, Error>> {
+        flatten_pages(self.all_pages(client, limit, offset))
+    }
+
+    /// Fetch all station genres page by page
+    ///
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    pub fn all_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<StationGenre>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             format!(
                 "/v1/catalog/{storefront}/station-genres",
@@ -1,16 +1,20 @@
 //! Genre
 
 use crate::error::Error;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of genre ids per request
+const MAX_GENRE_IDS: usize = 25;
+
 /// Genre
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -56,7 +60,17 @@ pub type GenreGetRequestBuilder<'a> = MusicRequestBuilder<'a, GenreRequestBuilde
 
 impl<'a> GenreGetRequestBuilder<'a> {
     /// Fetch one genre by id
-    pub async fn one(mut self, client: &ApiClient, id: &str) -> Result<Option<Genre>, Error> {
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<Genre>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one genre by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<Genre>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
@@ -64,33 +78,58 @@ impl<'a> GenreGetRequestBuilder<'a> {
                 "/v1/catalog/{storefront}/genres/{id}",
                 storefront = request_context.storefront.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let response = try_resource_response(response).await?;
-        Ok(response.data.into_iter().next())
+        let response = try_resource_response(response)?;
+        Ok(response)
     }
 
     /// Fetch multiple genres by id
-    pub async fn many(mut self, client: &ApiClient, ids: &[&str]) -> Result<Vec<Genre>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
+    pub async fn many(
+        self,
+        client: &ApiClient,
+        ids: &[&str],
+    ) -> Result<ManyResponse<Genre>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get(&format!(
-                "/v1/catalog/{storefront}/genres",
-                storefront = request_context.storefront.alpha2.to_lowercase()
-            ))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple genres by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Genre, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
+
+    /// Fetch multiple genres by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Genre>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!(
+            "/v1/catalog/{storefront}/genres",
+            storefront = request_context.storefront.alpha2.to_lowercase()
+        );
 
-        let response = try_resource_response(response).await?;
-        Ok(response.data)
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_GENRE_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch all genres for the current top charts    
@@ -100,17 +139,32 @@ impl<'a> GenreGetRequestBuilder<'a> {
     ///
     /// * offset - query offset
     pub async fn top_charts(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Genre, Error>> {
+        flatten_pages(self.top_charts_pages(client, limit, offset).await)
+    }
+
+    /// Fetch all genres for the current top charts page by page
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    pub async fn top_charts_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Genre>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             format!(
                 "/v1/catalog/{storefront}/genres",
@@ -3,7 +3,7 @@
 use crate::error::Error;
 use crate::primitive::TrackType;
 use crate::request::builder::MusicRequestBuilder;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::resource::Resource;
 use crate::ApiClient;
 use futures::Stream;
@@ -35,17 +35,35 @@ impl<'a> HistoryGetRequestBuilder<'a> {
     ///
     /// Possible resources: any
     pub async fn heavy_rotation(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Resource, Error>> {
+        flatten_pages(self.heavy_rotation_pages(client, limit, offset).await)
+    }
+
+    /// Fetch heavy rotation content page by page
+    ///
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    ///
+    /// Possible resources: any
+    pub async fn heavy_rotation_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Resource>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/history/heavy-rotation"),
             request_context,
@@ -63,17 +81,35 @@ impl<'a> HistoryGetRequestBuilder<'a> {
     ///
     /// Possible resources: any
     pub async fn recently_played(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Resource, Error>> {
+        flatten_pages(self.recently_played_pages(client, limit, offset).await)
+    }
+
+    /// Fetch recently played resources page by page
+    ///
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    ///
+    /// Possible resources: any
+    pub async fn recently_played_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Resource>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/recent/played"),
             request_context,
@@ -91,12 +127,34 @@ impl<'a> HistoryGetRequestBuilder<'a> {
     ///
     /// Possible resources: [`LibraryMusicVideo`], [`LibrarySong`], [`MusicVideo`], [`Song`]
     pub async fn recently_played_tracks(
-        mut self,
+        self,
         client: &ApiClient,
         tracks: &[TrackType],
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Resource, Error>> {
+        flatten_pages(
+            self.recently_played_tracks_pages(client, tracks, limit, offset)
+                .await,
+        )
+    }
+
+    /// Fetch recently played tracks page by page
+    ///
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    ///
+    /// Possible resources: [`LibraryMusicVideo`], [`LibrarySong`], [`MusicVideo`], [`Song`]
+    pub async fn recently_played_tracks_pages(
+        mut self,
+        client: &ApiClient,
+        tracks: &[TrackType],
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Resource>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
@@ -110,7 +168,7 @@ impl<'a> HistoryGetRequestBuilder<'a> {
                 .join(","),
         ));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/recent/played/tracks"),
             request_context,
@@ -128,17 +186,38 @@ impl<'a> HistoryGetRequestBuilder<'a> {
     ///
     /// Possible resources: [`Station`]
     pub async fn recently_played_stations(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Resource, Error>> {
+        flatten_pages(
+            self.recently_played_stations_pages(client, limit, offset)
+                .await,
+        )
+    }
+
+    /// Fetch recently played radio stations page by page
+    ///
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    ///
+    /// Possible resources: [`Station`]
+    pub async fn recently_played_stations_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Resource>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/recent/radio-stations"),
             request_context,
@@ -155,17 +234,37 @@ impl<'a> HistoryGetRequestBuilder<'a> {
     ///
     /// Possible resources: [`LibraryAlbum`], [`LibraryArtist`], [`LibraryPlaylist`], [`LibrarySong`]
     pub async fn recently_added_to_library(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Resource, Error>> {
+        flatten_pages(
+            self.recently_added_to_library_pages(client, limit, offset)
+                .await,
+        )
+    }
+
+    /// Fetch resources recently added to the library page by page
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    ///
+    /// Possible resources: [`LibraryAlbum`], [`LibraryArtist`], [`LibraryPlaylist`], [`LibrarySong`]
+    pub async fn recently_added_to_library_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Resource>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/library/recently-added"),
             request_context,
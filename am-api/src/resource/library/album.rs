@@ -2,15 +2,16 @@
 
 use crate::error::Error;
 use crate::primitive::{ContentRating, PlayParameters};
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
 use crate::resource::artwork::Artwork;
 use crate::resource::catalog::album::Album;
 use crate::resource::library::artist::LibraryArtist;
 use crate::resource::relationship::Relationship;
-use crate::resource::{Resource, ResourceHeader};
+use crate::resource::{Resource, ResourceHeader, ResourceResponse};
 use crate::time::year_or_date::YearOrDate;
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
@@ -18,6 +19,8 @@ use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of library album ids per request
+const MAX_LIBRARY_ALBUM_IDS: usize = 100;
 
 /// Library album
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
@@ -96,44 +99,74 @@ pub type LibraryAlbumGetRequestBuilder<'a> = MusicRequestBuilder<'a, LibraryAlbu
 impl<'a> LibraryAlbumGetRequestBuilder<'a> {
     /// Fetch one library album by id
     pub async fn one(
-        mut self,
+        self,
         client: &ApiClient,
         id: &str,
     ) -> Result<Option<LibraryAlbum>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one library album by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<LibraryAlbum>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
             .get(&format!("/v1/me/library/albums/{id}"))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple library albums by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<LibraryAlbum>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<LibraryAlbum>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get("/v1/me/library/albums")
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple library albums by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<LibraryAlbum, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple library albums by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<LibraryAlbum>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = String::from("/v1/me/library/albums");
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_LIBRARY_ALBUM_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch all library albums
@@ -143,17 +176,32 @@ impl<'a> LibraryAlbumGetRequestBuilder<'a> {
     ///
     /// * offset - query offset
     pub fn all(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<LibraryAlbum, Error>> {
+        flatten_pages(self.all_pages(client, limit, offset))
+    }
+
+    /// Fetch all library albums page by page
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    pub fn all_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<LibraryAlbum>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/library/albums"),
             request_context,
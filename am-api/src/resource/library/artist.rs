@@ -1,20 +1,24 @@
 //! Library artist
 
 use crate::error::Error;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
 use crate::resource::catalog::artist::Artist;
 use crate::resource::library::album::LibraryAlbum;
 use crate::resource::relationship::Relationship;
-use crate::resource::{ResourceHeader};
+use crate::resource::{ResourceHeader, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of library artist ids per request
+const MAX_LIBRARY_ARTIST_IDS: usize = 100;
+
 /// Library artist
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -74,44 +78,74 @@ pub type LibraryArtistGetRequestBuilder<'a> = MusicRequestBuilder<'a, LibraryArt
 impl<'a> LibraryArtistGetRequestBuilder<'a> {
     /// Fetch one library artist by id
     pub async fn one(
-        mut self,
+        self,
         client: &ApiClient,
         id: &str,
     ) -> Result<Option<LibraryArtist>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one library artist by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<LibraryArtist>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
             .get(&format!("/v1/me/library/artists/{id}"))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple library artists by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<LibraryArtist>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<LibraryArtist>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get("/v1/me/library/albums")
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple library artists by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<LibraryArtist, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple library artists by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<LibraryArtist>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = String::from("/v1/me/library/artists");
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_LIBRARY_ARTIST_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch all library artists
@@ -121,17 +155,32 @@ impl<'a> LibraryArtistGetRequestBuilder<'a> {
     ///
     /// * offset - query offset
     pub fn all(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<LibraryArtist, Error>> {
+        flatten_pages(self.all_pages(client, limit, offset))
+    }
+
+    /// Fetch all library artists page by page
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    pub fn all_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<LibraryArtist>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/library/artists"),
             request_context,
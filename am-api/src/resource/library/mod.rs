@@ -7,9 +7,13 @@ use crate::resource::{Resource, ResourceInfo, ResourceType};
 use crate::ApiClient;
 
 use crate::request::builder::MusicRequestBuilder;
+use futures::{stream, StreamExt, TryStreamExt};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Maximum amount of ids of each resource type per library add request
+const MAX_ADD_IDS: usize = 100;
+
 pub mod album;
 pub mod artist;
 pub mod music_video;
@@ -53,29 +57,61 @@ impl<'a> LibraryAddResourceBuilder<'a> {
     }
 
     /// Send the request
+    ///
+    /// Ids are split into chunks that are sent concurrently
     pub async fn send(mut self, client: &ApiClient) -> Result<Vec<Resource>, Error> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("representation"), String::from("ids")));
 
-        for (resource_type, ids) in self.data {
-            request_context.query.push((
-                format!("ids[{}]", resource_type),
-                ids.into_iter().collect::<Vec<_>>().join(","),
-            ));
-        }
+        let ids = self
+            .data
+            .into_iter()
+            .map(|(resource_type, ids)| (resource_type, ids.into_iter().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        let chunks = ids
+            .iter()
+            .map(|(_, ids)| ids.len().div_ceil(MAX_ADD_IDS))
+            .max()
+            .unwrap_or_default()
+            .max(1);
+
+        stream::iter(0..chunks)
+            .map(|chunk| {
+                let mut request_context = request_context.clone();
+                for (resource_type, ids) in &ids {
+                    let ids = ids
+                        .iter()
+                        .skip(chunk * MAX_ADD_IDS)
+                        .take(MAX_ADD_IDS)
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+
+                    if !ids.is_empty() {
+                        request_context
+                            .query
+                            .push((format!("ids[{}]", resource_type), ids.join(",")));
+                    }
+                }
 
-        let request_context = Arc::new(request_context);
+                async move {
+                    let request_context = Arc::new(request_context);
 
-        let response = client
-            .post("/v1/me/library")
-            .query(&request_context.query)
-            .send()
-            .await?;
+                    let response = client
+                        .post("/v1/me/library")
+                        .context(&request_context)
+                        .send()
+                        .await?;
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+                    let mut response = try_resource_response(response)?;
+                    response.data.set_context(request_context);
+                    Ok::<_, Error>(response.data)
+                }
+            })
+            .buffered(self.concurrency.max(1))
+            .try_concat()
+            .await
     }
 }
@@ -2,15 +2,18 @@
 
 use crate::error::Error;
 use crate::primitive::PlayParameters;
+use crate::request::batch::{
+    collect_many, fetch_many, flatten_many, ManyResponse, DEFAULT_CONCURRENCY,
+};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
 use crate::resource::artwork::Artwork;
 use crate::resource::attributes::DescriptionAttribute;
 use crate::resource::catalog::playlist::Playlist;
 use crate::resource::relationship::Relationship;
-use crate::resource::{ErrorResponse, Resource, ResourceHeader, ResourceInfo, ResourceType};
+use crate::resource::{Resource, ResourceHeader, ResourceInfo, ResourceResponse, ResourceType};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
 use futures::Stream;
@@ -19,6 +22,12 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use time::OffsetDateTime;
 
+/// Maximum amount of library playlist ids per request
+const MAX_LIBRARY_PLAYLIST_IDS: usize = 100;
+
+/// Maximum amount of library playlist folder ids per request
+const MAX_LIBRARY_PLAYLIST_FOLDER_IDS: usize = 25;
+
 /// Library playlist
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -79,10 +88,7 @@ impl LibraryPlaylist {
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        response.error_for_status()?;
 
         Ok(())
     }
@@ -155,45 +161,71 @@ pub type LibraryPlaylistGetRequestBuilder<'a> =
 
 impl<'a> LibraryPlaylistGetRequestBuilder<'a> {
     /// Fetch one library playlist by id
-    pub async fn one(
+    pub async fn one(self, client: &ApiClient, id: &str) -> Result<Option<LibraryPlaylist>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one library playlist by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
         mut self,
         client: &ApiClient,
         id: &str,
-    ) -> Result<Option<LibraryPlaylist>, Error> {
+    ) -> Result<ResourceResponse<LibraryPlaylist>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
             .get(&format!("/v1/me/library/playlists/{id}"))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple library playlists by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<LibraryPlaylist>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<LibraryPlaylist>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get("/v1/me/library/playlists")
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple library playlists by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<LibraryPlaylist, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple library playlists by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<LibraryPlaylist>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = String::from("/v1/me/library/playlists");
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_LIBRARY_PLAYLIST_IDS,
+            self.concurrency,
+        )
     }
 
     /// Fetch all library playlists
@@ -203,17 +235,32 @@ impl<'a> LibraryPlaylistGetRequestBuilder<'a> {
     ///
     /// * offset - query offset
     pub fn all(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<LibraryPlaylist, Error>> {
+        flatten_pages(self.all_pages(client, limit, offset))
+    }
+
+    /// Fetch all library playlists page by page
+    /// # Params
+    ///
+    /// * limit - limit of entries per query
+    ///
+    /// * offset - query offset
+    pub fn all_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<LibraryPlaylist>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/me/library/playlists"),
             request_context,
@@ -285,6 +332,8 @@ impl<'localization, 'name> LibraryPlaylistCreateBuilder<'localization, 'name> {
             extensions: Default::default(),
             relationships: Default::default(),
             views: Default::default(),
+            bypass_cache: false,
+            concurrency: DEFAULT_CONCURRENCY,
             data: LibraryPlaylistCreateRequest {
                 attributes: LibraryPlaylistCreateRequestAttributes {
                     name,
@@ -362,12 +411,12 @@ impl<'localization, 'name> LibraryPlaylistCreateBuilder<'localization, 'name> {
 
         let response = client
             .post("/v1/me/library/playlists")
-            .query(&request_context.query)
+            .context(&request_context)
             .json(&self.data)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data.into_iter().next())
     }
@@ -452,43 +501,73 @@ pub type LibraryPlaylistFolderGetRequestBuilder<'a> =
 impl<'a> LibraryPlaylistFolderGetRequestBuilder<'a> {
     /// Fetch one library playlist folder by id
     pub async fn one(
-        mut self,
+        self,
         client: &ApiClient,
         id: &str,
     ) -> Result<Option<LibraryPlaylistFolder>, Error> {
+        let response = self.one_response(client, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one library playlist folder by id, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        id: &str,
+    ) -> Result<ResourceResponse<LibraryPlaylistFolder>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let response = client
             .get(&format!("/v1/me/library/playlist-folders/{id}"))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple library playlist folders by id
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         ids: &[&str],
-    ) -> Result<Vec<LibraryPlaylistFolder>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<LibraryPlaylistFolder>, Error> {
+        collect_many(self.many_chunks(client, ids)).await
+    }
 
-        let response = client
-            .get("/v1/me/library/playlist-folders/")
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple library playlist folders by id as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<LibraryPlaylistFolder, Error>> + 'b {
+        flatten_many(self.many_chunks(client, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple library playlist folders by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<LibraryPlaylistFolder>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = String::from("/v1/me/library/playlist-folders/");
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_LIBRARY_PLAYLIST_FOLDER_IDS,
+            self.concurrency,
+        )
     }
 }
@@ -9,7 +9,6 @@ use crate::resource::library::music_video::LibraryMusicVideo;
 use crate::resource::library::playlist::LibraryPlaylist;
 use crate::resource::library::song::LibrarySong;
 use crate::resource::relationship::Relationship;
-use crate::resource::ErrorResponse;
 use crate::ApiClient;
 use am_api_proc_macro::Context;
 use serde::{Deserialize, Serialize};
@@ -58,16 +57,13 @@ impl<'a> LibrarySearchRequestBuilder<'a> {
 
         let response = client
             .get("/v1/me/library/search")
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        let response = response.error_for_status()?;
 
-        let mut response = response.json::<LibrarySearchResponse>().await?;
+        let mut response = response.json::<LibrarySearchResponse>()?;
         response.results.set_context(request_context);
         Ok(response.results)
     }
@@ -2,6 +2,7 @@
 use am_api_proc_macro::Context;
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub mod artwork;
 pub mod attributes;
@@ -42,6 +43,7 @@ macro_rules! resource {
         /// Apple music resource data
         #[derive(Context, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
         #[serde(tag = "type")]
+        #[allow(clippy::large_enum_variant)]
         pub enum Resource {
             $(
                 #[doc = $name]
@@ -71,6 +73,12 @@ macro_rules! resource {
         }
 
         $(
+            impl ResourceInfo for $data_type {
+                fn get_header(&self) -> &ResourceHeader {
+                    &self.header
+                }
+            }
+
             impl From<$data_type> for Resource {
                 fn from(data: $data_type) -> Self {
                     Self::$enum_name { data }
@@ -109,6 +117,25 @@ resource! {
 pub struct ResourceResponse<R = Resource> {
     /// Data
     pub data: Vec<R>,
+    /// A relative cursor to fetch the next page of resources if more exist
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Response metadata
+    #[serde(default)]
+    pub meta: ResponseMeta,
+    /// Set when the api answered `304 Not Modified` and the data is unchanged since the previous response
+    #[serde(skip)]
+    pub not_modified: bool,
+}
+
+/// Apple music response metadata
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResponseMeta {
+    /// Total amount of resources in the collection
+    pub total: Option<usize>,
+    /// Resources matched by each value of a `filter[...]` query, keyed by filter name and value
+    pub filters: BTreeMap<String, BTreeMap<String, Vec<ResourceHeader>>>,
 }
 
 /// Apple music error response
@@ -140,4 +167,16 @@ pub struct MusicError {
     pub status: String,
     /// Error code
     pub code: String,
+    /// Part of the request that caused the error
+    pub source: Option<ErrorSource>,
+}
+
+/// Part of a request an apple music error is about
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ErrorSource {
+    /// Query parameter that caused the error
+    pub parameter: Option<String>,
+    /// Json pointer to the part of the request body that caused the error
+    pub pointer: Option<String>,
 }
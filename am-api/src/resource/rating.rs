@@ -1,17 +1,22 @@
 //! Rating
 
 use crate::error::Error;
+use crate::request::batch::{collect_many, fetch_many, flatten_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
 use crate::request::try_resource_response;
 use crate::resource::relationship::Relationship;
-use crate::resource::{ErrorResponse, Resource, ResourceHeader, ResourceInfo, ResourceType};
+use crate::resource::{Resource, ResourceHeader, ResourceInfo, ResourceResponse, ResourceType};
 use crate::ApiClient;
 use am_api_proc_macro::{Context, ResourceProperty};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+/// Maximum amount of rating ids per request
+const MAX_RATING_IDS: usize = 25;
+
 /// Rating
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -117,50 +122,81 @@ pub type RatingGetRequestBuilder<'a> = MusicRequestBuilder<'a, RatingGetRequestB
 impl<'a> RatingGetRequestBuilder<'a> {
     /// Fetch one rating for a resource
     pub async fn one(
-        mut self,
+        self,
         client: &ApiClient,
         rating_type: RatingType,
         id: &str,
     ) -> Result<Option<Rating>, Error> {
+        let response = self.one_response(client, rating_type, id).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one rating for a resource, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        rating_type: RatingType,
+        id: &str,
+    ) -> Result<ResourceResponse<Rating>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
 
         let endpoint = rating_type.to_string();
 
         let response = client
             .get(&format!("/v1/me/ratings/{endpoint}/{id}"))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
-        Ok(response.data.into_iter().next())
+        Ok(response)
     }
 
     /// Fetch multiple ratings by ids
+    ///
+    /// Ids are split into chunks that are fetched concurrently, results keep the order of the ids.
+    /// Ids that did not resolve to a resource are reported in [`ManyResponse::unresolved`]
     pub async fn many(
-        mut self,
+        self,
         client: &ApiClient,
         rating_type: RatingType,
         ids: &[&str],
-    ) -> Result<Vec<Rating>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context
-            .query
-            .push((String::from("ids"), ids.to_vec().join(",")));
-        let request_context = Arc::new(request_context);
-
-        let endpoint = rating_type.to_string();
+    ) -> Result<ManyResponse<Rating>, Error> {
+        collect_many(self.many_chunks(client, rating_type, ids)).await
+    }
 
-        let response = client
-            .get(&format!("/v1/me/ratings/{endpoint}"))
-            .query(&request_context.query)
-            .send()
-            .await?;
+    /// Fetch multiple ratings by ids as a stream, for very large amounts of ids
+    ///
+    /// Unresolved ids are skipped, use [`many`](Self::many) to report them
+    pub fn many_stream<'b>(
+        self,
+        client: &'b ApiClient,
+        rating_type: RatingType,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<Rating, Error>> + 'b {
+        flatten_many(self.many_chunks(client, rating_type, ids))
+    }
 
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+    /// Fetch multiple ratings by id in chunks
+    fn many_chunks<'b>(
+        mut self,
+        client: &'b ApiClient,
+        rating_type: RatingType,
+        ids: &'b [&'b str],
+    ) -> impl Stream<Item = Result<ManyResponse<Rating>, Error>> + 'b {
+        let request_context = self.get_request_context_drain(client);
+        let endpoint = format!("/v1/me/ratings/{rating_type}");
+
+        fetch_many(
+            client,
+            endpoint,
+            request_context,
+            "ids",
+            ids,
+            MAX_RATING_IDS,
+            self.concurrency,
+        )
     }
 }
 
@@ -191,11 +227,11 @@ impl<'a> RatingPostRequestBuilder<'a> {
                 "/v1/me/ratings/{endpoint}/{id}",
                 id = resource.get_header().id
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data.into_iter().next())
     }
@@ -216,14 +252,11 @@ impl<'a> RatingPostRequestBuilder<'a> {
                 "/v1/me/ratings/{endpoint}/{id}",
                 id = resource.get_header().id
             ))
-            .query(&request_context.query)
+            .context(&request_context)
             .send()
             .await?;
 
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
+        response.error_for_status()?;
 
         Ok(())
     }
@@ -1,17 +1,21 @@
 //! Storefront
 
 use crate::error::Error;
+use crate::request::batch::{collect_many, fetch_many, ManyResponse};
 use crate::request::builder::MusicRequestBuilder;
 use crate::request::context::ContextContainer;
-use crate::request::paginated::paginate;
+use crate::request::paginated::{flatten_pages, paginate_pages, Page};
 use crate::request::try_resource_response;
-use crate::resource::ResourceHeader;
+use crate::resource::{ResourceHeader, ResourceInfo, ResourceResponse};
 use crate::ApiClient;
 use am_api_proc_macro::Context;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Maximum amount of storefront ids per request
+const MAX_STOREFRONT_IDS: usize = 100;
+
 /// Storefront
 #[derive(Context, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -26,11 +30,22 @@ pub struct Storefront {
     pub attributes: Option<StorefrontAttributes>,
 }
 
+impl ResourceInfo for Storefront {
+    fn get_header(&self) -> &ResourceHeader {
+        &self.header
+    }
+}
+
 impl Storefront {
     /// Get storefront request builder
     pub fn get<'a>() -> StorefrontGetRequestBuilder<'a> {
         StorefrontGetRequestBuilder::default()
     }
+
+    /// Get the country of this storefront
+    pub fn country(&self) -> Option<celes::Country> {
+        celes::Country::from_alpha2(&self.header.id).ok()
+    }
 }
 
 /// Storefront attributes
@@ -71,21 +86,45 @@ pub type StorefrontGetRequestBuilder<'a> =
 impl<'a> StorefrontGetRequestBuilder<'a> {
     /// Fetch one storefront using a country
     pub async fn one(
-        mut self,
+        self,
         client: &ApiClient,
         country: celes::Country,
     ) -> Result<Option<Storefront>, Error> {
+        let response = self.one_response(client, country).await?;
+        Ok(response.data.into_iter().next())
+    }
+
+    /// Fetch one storefront using a country, keeping the response metadata such as [`ResourceResponse::not_modified`]
+    pub async fn one_response(
+        mut self,
+        client: &ApiClient,
+        country: celes::Country,
+    ) -> Result<ResourceResponse<Storefront>, Error> {
         let request_context = Arc::new(self.get_request_context_drain(client));
         let response = client
             .get(&format!(
                 "/v1/storefronts/{}",
                 country.alpha2.to_lowercase()
             ))
-            .query(&request_context.query)
+            .context(&request_context)
+            .send()
+            .await?;
+
+        let mut response = try_resource_response(response)?;
+        response.data.set_context(request_context);
+        Ok(response)
+    }
+
+    /// Fetch the storefront of the user the media user token belongs to
+    pub async fn me(mut self, client: &ApiClient) -> Result<Option<Storefront>, Error> {
+        let request_context = Arc::new(self.get_request_context_drain(client));
+        let response = client
+            .get("/v1/me/storefront")
+            .context(&request_context)
             .send()
             .await?;
 
-        let mut response = try_resource_response(response).await?;
+        let mut response = try_resource_response(response)?;
         response.data.set_context(request_context);
         Ok(response.data.into_iter().next())
     }
@@ -101,42 +140,49 @@ impl<'a> StorefrontGetRequestBuilder<'a> {
         mut self,
         client: &ApiClient,
         countries: &[celes::Country],
-    ) -> Result<Vec<Storefront>, Error> {
-        let mut request_context = self.get_request_context_drain(client);
-        request_context.query.push((
-            String::from("ids"),
-            countries
-                .iter()
-                .map(|e| e.alpha2.to_lowercase())
-                .collect::<Vec<_>>()
-                .join(","),
-        ));
-        let request_context = Arc::new(request_context);
+    ) -> Result<ManyResponse<Storefront>, Error> {
+        let request_context = self.get_request_context_drain(client);
+        let ids = countries
+            .iter()
+            .map(|e| e.alpha2.to_lowercase())
+            .collect::<Vec<_>>();
+        let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
 
-        let response = client
-            .get("/v1/storefronts")
-            .query(&request_context.query)
-            .send()
-            .await?;
-
-        let mut response = try_resource_response(response).await?;
-        response.data.set_context(request_context);
-        Ok(response.data)
+        collect_many(fetch_many(
+            client,
+            String::from("/v1/storefronts"),
+            request_context,
+            "ids",
+            &ids,
+            MAX_STOREFRONT_IDS,
+            self.concurrency,
+        ))
+        .await
     }
 
     /// Fetch all storefronts
     pub fn all(
-        mut self,
+        self,
         client: &ApiClient,
         limit: usize,
         offset: usize,
     ) -> impl Stream<Item = Result<Storefront, Error>> {
+        flatten_pages(self.all_pages(client, limit, offset))
+    }
+
+    /// Fetch all storefronts page by page
+    pub fn all_pages(
+        mut self,
+        client: &ApiClient,
+        limit: usize,
+        offset: usize,
+    ) -> impl Stream<Item = Result<Page<Storefront>, Error>> {
         let mut request_context = self.get_request_context_drain(client);
         request_context
             .query
             .push((String::from("limit"), limit.to_string()));
 
-        paginate(
+        paginate_pages(
             client.clone(),
             String::from("/v1/storefronts"),
             request_context,
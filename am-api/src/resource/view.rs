@@ -2,15 +2,16 @@
 
 use crate::error::Error;
 use crate::request::context::{ContextContainer, RequestContext};
-use crate::resource::ErrorResponse;
+use crate::request::paginated::{flatten_pages, follow, Cursor, Page};
+use crate::trace;
 use crate::ApiClient;
 use async_stream::try_stream;
-use futures::Stream;
-use reqwest::{Response};
+use futures::{Stream, TryStreamExt};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
+use std::pin::pin;
 use std::sync::Arc;
 
 /// Apple music view
@@ -39,42 +40,49 @@ where
     T: Clone + DeserializeOwned + ContextContainer,
 {
     /// Iterate this view
+    ///
+    /// Fails with [`Error::MissingContext`] if the view was deserialized without a context,
+    /// see [`AttachContext`](crate::request::AttachContext)
     pub fn iter(&self, client: &ApiClient) -> impl Stream<Item = Result<T, Error>> {
+        flatten_pages(self.pages(client))
+    }
+
+    /// Iterate this view page by page, starting with the data already on it
+    ///
+    /// Each page carries a [`Cursor`](crate::request::paginated::Cursor) that can be resumed with [`resume`](crate::request::paginated::resume)
+    pub fn pages(&self, client: &ApiClient) -> impl Stream<Item = Result<Page<T>, Error>> {
         let view = self.clone();
         let client = client.clone();
-        let context = view
-            .context
-            .clone()
-            .expect("context should always exist on views");
+
+        let span = trace::pagination_span(view.href.as_deref().unwrap_or_default());
 
         try_stream! {
-            let mut view = view;
+            let context = view.context.clone().ok_or(Error::MissingContext)?;
+            let cursor = view
+                .next
+                .as_deref()
+                .map(|next| Cursor::from_next(next, &context));
 
-            loop {
-                for mut entry in view.data {
-                    entry.set_context(context.clone());
-                    yield entry;
-                }
+            let mut items = view.data;
+            items.set_context(context.clone());
+            yield Page {
+                items,
+                total: None,
+                next_offset: cursor.as_ref().and_then(Cursor::get_offset),
+                cursor: cursor.clone(),
+                not_modified: false,
+            };
 
-                let Some(next) = view.next.as_ref() else {
-                    return;
-                };
+            let Some(cursor) = cursor else {
+                return;
+            };
 
-                let response = client.get(next.as_str()).query(&context.query).send().await?;
-                view = Self::try_view_response(response).await?;
+            let mut pages = pin!(follow(client, cursor, (*context).clone(), span));
+            while let Some(page) = pages.try_next().await? {
+                yield page;
             }
         }
     }
-
-    async fn try_view_response(response: Response) -> Result<Self, Error> {
-        if !response.status().is_success() {
-            let error_response: ErrorResponse = response.json().await?;
-            return Err(Error::MusicError(error_response));
-        }
-
-        let result = response.json().await?;
-        Ok(result)
-    }
 }
 
 impl<Attributes, T> ContextContainer for View<Attributes, T>
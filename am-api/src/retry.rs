@@ -0,0 +1,128 @@
+//! Request retry policy
+
+use crate::error::Error;
+use crate::transport::TransportResponse;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
+
+/// Retry policy for requests sent by [`ApiClient`](crate::ApiClient)
+///
+/// Requests are retried on `429 Too Many Requests`, `5xx` responses and connection errors,
+/// using exponential backoff. A `Retry-After` header sent by the server takes precedence over the computed backoff,
+/// requests asked to wait longer than `max_backoff` are not retried and fail with the server's answer
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum amount of attempts for a request, including the first one
+    pub max_attempts: u32,
+    /// Backoff before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff and for the `Retry-After` delays waited for
+    pub max_backoff: Duration,
+    /// Factor the backoff is multiplied by after every attempt
+    pub multiplier: f64,
+    /// Randomize the backoff between zero and the computed value
+    pub jitter: bool,
+    /// Retry non-idempotent requests, such as POST, as well
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Create a policy that never retries
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Get the delay before retrying a request, or [`None`] if it should not be retried
+    ///
+    /// # Params
+    ///
+    /// * attempt - attempt that produced the result, starting at 1
+    pub fn retry_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        result: &Result<TransportResponse, Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        if !self.retry_non_idempotent && !is_idempotent(method) {
+            return None;
+        }
+
+        let retry_after = match result {
+            Ok(response) if is_retryable_status(response.status) => {
+                parse_retry_after(&response.headers)
+            }
+            Err(Error::Reqwest(e)) if e.is_connect() || e.is_timeout() => None,
+            _ => return None,
+        };
+
+        match retry_after {
+            // waiting longer than the policy allows is left to the caller
+            Some(retry_after) if retry_after > self.max_backoff => None,
+            Some(retry_after) => Some(retry_after),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Compute the backoff after an attempt
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = (self.initial_backoff.as_secs_f64()
+            * self.multiplier.max(1.0).powi(exponent))
+        .min(self.max_backoff.as_secs_f64());
+        let backoff = Duration::try_from_secs_f64(backoff).unwrap_or(self.max_backoff);
+
+        match self.jitter {
+            true => backoff.mul_f64(fastrand::f64()),
+            false => backoff,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// Check if a method is idempotent
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+/// Check if a status is worth retrying
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Parse a `Retry-After` header, either in delay seconds or http date format
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let delay = date - OffsetDateTime::now_utc();
+    Some(delay.try_into().unwrap_or_default())
+}
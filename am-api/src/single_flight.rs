@@ -0,0 +1,51 @@
+//! Coalescing of identical in-flight requests
+
+use crate::cache::CacheKey;
+use crate::error::Error;
+use crate::transport::TransportResponse;
+use futures::future::{BoxFuture, Shared};
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+type SharedResponse = Shared<BoxFuture<'static, Result<TransportResponse, Arc<Error>>>>;
+
+/// Map of in-flight requests, concurrent requests with the same key share one response
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Arc<Mutex<HashMap<CacheKey, SharedResponse>>>,
+}
+
+impl SingleFlight {
+    /// Run `request`, or wait for an identical request that is already in flight
+    pub(crate) async fn run<F>(&self, key: CacheKey, request: F) -> Result<TransportResponse, Error>
+    where
+        F: Future<Output = Result<TransportResponse, Error>> + Send + 'static,
+    {
+        let shared = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+
+            in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let in_flight = self.in_flight.clone();
+                    async move {
+                        let result = request.await.map_err(Arc::new);
+                        in_flight
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .remove(&key);
+                        result
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        shared
+            .await
+            .map_err(|e| Arc::try_unwrap(e).unwrap_or_else(Error::Coalesced))
+    }
+}
@@ -0,0 +1,224 @@
+//! Developer token generation
+
+use crate::error::Error;
+use crate::token::TokenProvider;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::future::BoxFuture;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::pkcs8::DecodePrivateKey;
+use reqwest::header::HeaderValue;
+use serde::Serialize;
+use std::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Maximum developer token lifetime allowed by Apple
+pub const MAX_EXPIRY: Duration = Duration::from_secs(15_777_000);
+
+/// Default developer token lifetime
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(60 * 60 * 12);
+
+/// Default margin before expiry at which a developer token gets re-signed
+pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60 * 5);
+
+/// Developer token signed from a MusicKit private key
+///
+/// The signed token is cached and automatically re-signed when it gets close to expiring
+pub struct DeveloperToken {
+    team_id: String,
+    key_id: String,
+    signing_key: SigningKey,
+    expiry: Duration,
+    refresh_margin: Duration,
+    current: Mutex<Option<SignedDeveloperToken>>,
+}
+
+/// Signed developer token
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignedDeveloperToken {
+    /// Encoded jwt
+    pub token: String,
+    /// Time the token was issued at
+    pub issued_at: OffsetDateTime,
+    /// Time the token expires at
+    pub expires_at: OffsetDateTime,
+}
+
+#[derive(Serialize)]
+struct JwtHeader<'a> {
+    alg: &'static str,
+    kid: &'a str,
+}
+
+#[derive(Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+impl DeveloperToken {
+    /// Create a new [`DeveloperToken`] instance from a PKCS#8 PEM encoded private key, as found in a `.p8` file
+    pub fn from_pkcs8_pem(team_id: &str, key_id: &str, private_key: &str) -> Result<Self, Error> {
+        let signing_key = SigningKey::from_pkcs8_pem(private_key)?;
+        Ok(Self::new(team_id, key_id, signing_key))
+    }
+
+    /// Create a new [`DeveloperToken`] instance from a PKCS#8 DER encoded private key
+    pub fn from_pkcs8_der(team_id: &str, key_id: &str, private_key: &[u8]) -> Result<Self, Error> {
+        let signing_key = SigningKey::from_pkcs8_der(private_key)?;
+        Ok(Self::new(team_id, key_id, signing_key))
+    }
+
+    fn new(team_id: &str, key_id: &str, signing_key: SigningKey) -> Self {
+        DeveloperToken {
+            team_id: team_id.to_string(),
+            key_id: key_id.to_string(),
+            signing_key,
+            expiry: DEFAULT_EXPIRY,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Set the lifetime of signed tokens, clamped to [`MAX_EXPIRY`]
+    pub fn expiry(mut self, expiry: Duration) -> Self {
+        self.expiry = expiry.min(MAX_EXPIRY);
+        self
+    }
+
+    /// Set the margin before expiry at which the token gets re-signed, margins longer than the expiry re-sign on every call
+    pub fn refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Get the team id
+    pub fn get_team_id(&self) -> &str {
+        self.team_id.as_str()
+    }
+
+    /// Get the key id
+    pub fn get_key_id(&self) -> &str {
+        self.key_id.as_str()
+    }
+
+    /// Get a valid token, re-signing it if it is about to expire
+    pub fn token(&self) -> String {
+        let now = OffsetDateTime::now_utc();
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+
+        match current.as_ref() {
+            Some(signed) if self.is_fresh(signed, now) => signed.token.clone(),
+            _ => {
+                let signed = self.sign_at(now);
+                let token = signed.token.clone();
+                *current = Some(signed);
+                token
+            }
+        }
+    }
+
+    /// Check if a signed token is not within the refresh margin of expiring yet
+    fn is_fresh(&self, signed: &SignedDeveloperToken, now: OffsetDateTime) -> bool {
+        time::Duration::try_from(self.refresh_margin)
+            .ok()
+            .and_then(|margin| signed.expires_at.checked_sub(margin))
+            .is_some_and(|refresh_at| refresh_at > now)
+    }
+
+    /// Force the token to be re-signed on the next [`DeveloperToken::token`] call
+    pub fn invalidate(&self) {
+        *self.current.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Sign a new token issued at the specified time
+    pub fn sign_at(&self, issued_at: OffsetDateTime) -> SignedDeveloperToken {
+        let expires_at = issued_at + self.expiry;
+
+        let header = JwtHeader {
+            alg: "ES256",
+            kid: &self.key_id,
+        };
+        let claims = JwtClaims {
+            iss: &self.team_id,
+            iat: issued_at.unix_timestamp(),
+            exp: expires_at.unix_timestamp(),
+        };
+
+        let message = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header).expect("jwt header serializes")),
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).expect("jwt claims serialize"))
+        );
+
+        let signature: Signature = self.signing_key.sign(message.as_bytes());
+
+        SignedDeveloperToken {
+            token: format!(
+                "{}.{}",
+                message,
+                URL_SAFE_NO_PAD.encode(signature.to_bytes())
+            ),
+            issued_at,
+            expires_at,
+        }
+    }
+}
+
+impl Debug for DeveloperToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeveloperToken")
+            .field("team_id", &self.team_id)
+            .field("key_id", &self.key_id)
+            .field("expiry", &self.expiry)
+            .field("refresh_margin", &self.refresh_margin)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Token provider signing developer tokens with a [`DeveloperToken`]
+///
+/// Refreshing re-signs the developer token
+#[derive(Debug)]
+pub struct DeveloperTokenProvider {
+    developer_token: DeveloperToken,
+    media_user_token: Option<String>,
+}
+
+impl DeveloperTokenProvider {
+    /// Create a new [`DeveloperTokenProvider`] instance
+    pub fn new(
+        developer_token: DeveloperToken,
+        media_user_token: Option<&str>,
+    ) -> Result<DeveloperTokenProvider, Error> {
+        if let Some(media_user_token) = media_user_token {
+            HeaderValue::from_str(media_user_token)?;
+        }
+
+        Ok(DeveloperTokenProvider {
+            developer_token,
+            media_user_token: media_user_token.map(str::to_string),
+        })
+    }
+}
+
+impl TokenProvider for DeveloperTokenProvider {
+    fn developer_token(&self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async { Ok(self.developer_token.token()) })
+    }
+
+    fn media_user_token(&self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async { Ok(self.media_user_token.clone()) })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async {
+            self.developer_token.invalidate();
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Authentication tokens
+
+use crate::error::Error;
+use futures::future::BoxFuture;
+use reqwest::header::HeaderValue;
+use std::sync::Arc;
+
+pub mod developer;
+
+/// Provider of authentication tokens
+///
+/// The provider is consulted for every request sent by [`ApiClient`](crate::ApiClient),
+/// so tokens can be rotated without rebuilding the client
+pub trait TokenProvider: Send + Sync {
+    /// Get the developer token
+    fn developer_token(&self) -> BoxFuture<'_, Result<String, Error>>;
+
+    /// Get the media user token, [`None`] omits the `media-user-token` header
+    fn media_user_token(&self) -> BoxFuture<'_, Result<Option<String>, Error>>;
+
+    /// Refresh tokens after a request was rejected with `401 Unauthorized` or `403 Forbidden`
+    ///
+    /// The rejected request is replayed once if the refresh changed the developer or media user token
+    fn refresh(&self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for Arc<T> {
+    fn developer_token(&self) -> BoxFuture<'_, Result<String, Error>> {
+        self.as_ref().developer_token()
+    }
+
+    fn media_user_token(&self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        self.as_ref().media_user_token()
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<(), Error>> {
+        self.as_ref().refresh()
+    }
+}
+
+/// Token provider returning fixed tokens
+#[derive(Clone)]
+pub struct StaticTokenProvider {
+    developer_token: String,
+    media_user_token: Option<String>,
+}
+
+impl StaticTokenProvider {
+    /// Create a new [`StaticTokenProvider`] instance
+    pub fn new(
+        developer_token: &str,
+        media_user_token: Option<&str>,
+    ) -> Result<StaticTokenProvider, Error> {
+        HeaderValue::from_str(developer_token)?;
+        if let Some(media_user_token) = media_user_token {
+            HeaderValue::from_str(media_user_token)?;
+        }
+
+        Ok(StaticTokenProvider {
+            developer_token: developer_token.to_string(),
+            media_user_token: media_user_token.map(str::to_string),
+        })
+    }
+}
+
+impl TokenProvider for StaticTokenProvider {
+    fn developer_token(&self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async { Ok(self.developer_token.clone()) })
+    }
+
+    fn media_user_token(&self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async { Ok(self.media_user_token.clone()) })
+    }
+}
@@ -0,0 +1,148 @@
+//! Optional `tracing` instrumentation, compiled to no-ops without the `tracing` feature
+
+use crate::error::Error;
+#[cfg(feature = "tracing")]
+use crate::transport::endpoint_template;
+use crate::transport::{TransportRequest, TransportResponse};
+use std::future::Future;
+use std::time::Duration;
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::Span;
+
+/// Placeholder span used without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+#[derive(Debug, Clone)]
+pub(crate) struct Span;
+
+/// Header values that are never recorded
+#[cfg(feature = "tracing")]
+const REDACTED_HEADERS: [&str; 2] = ["authorization", "media-user-token"];
+
+/// Create a span for an http call
+#[allow(unused_variables)]
+pub(crate) fn request_span(request: &TransportRequest, base_url: &str) -> Span {
+    #[cfg(feature = "tracing")]
+    {
+        let path = request.path(base_url);
+        let span = tracing::debug_span!(
+            "am_api.request",
+            method = %request.method,
+            endpoint = %endpoint_template(path),
+            storefront = tracing::field::Empty,
+            attempt = tracing::field::Empty,
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        );
+        if let Some(storefront) = storefront(path) {
+            span.record("storefront", storefront);
+        }
+        span
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    Span
+}
+
+/// Record an attempt of an http call about to be sent
+#[allow(unused_variables)]
+pub(crate) fn record_attempt(span: &Span, attempt: u32, request: &TransportRequest) {
+    #[cfg(feature = "tracing")]
+    {
+        span.record("attempt", attempt);
+        tracing::trace!(
+            parent: span,
+            headers = ?redact_headers(&request.headers),
+            "sending request"
+        );
+    }
+}
+
+/// Record the outcome of an attempt
+#[allow(unused_variables)]
+pub(crate) fn record_response(
+    span: &Span,
+    result: &Result<TransportResponse, Error>,
+    latency: Duration,
+) {
+    #[cfg(feature = "tracing")]
+    {
+        span.record("latency_ms", latency.as_millis() as u64);
+        match result {
+            Ok(response) => {
+                span.record("status", response.status.as_u16());
+            }
+            Err(e) => tracing::debug!(parent: span, error = %e, "request failed"),
+        }
+    }
+}
+
+/// Record that an attempt is retried after a delay
+#[allow(unused_variables)]
+pub(crate) fn record_retry(span: &Span, attempt: u32, delay: Duration) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        parent: span,
+        attempt,
+        delay_ms = delay.as_millis() as u64,
+        "retrying request"
+    );
+}
+
+/// Record that a response could not be stored in the disk cache
+#[allow(unused_variables)]
+pub(crate) fn record_disk_cache_error(path: &str, error: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(path, error = %error, "failed to store response in disk cache");
+}
+
+/// Create a parent span for the page requests of a paginated stream
+#[allow(unused_variables)]
+pub(crate) fn pagination_span(endpoint: &str) -> Span {
+    #[cfg(feature = "tracing")]
+    return tracing::debug_span!("am_api.paginate", endpoint = %endpoint_template(endpoint));
+
+    #[cfg(not(feature = "tracing"))]
+    Span
+}
+
+/// Create a span for a single page request of a paginated stream
+#[allow(unused_variables)]
+pub(crate) fn page_span(parent: &Span, offset: Option<usize>) -> Span {
+    #[cfg(feature = "tracing")]
+    return tracing::debug_span!(parent: parent, "am_api.page", offset);
+
+    #[cfg(not(feature = "tracing"))]
+    Span
+}
+
+/// Run a future inside a span
+#[allow(unused_variables)]
+pub(crate) fn instrument<F: Future>(future: F, span: &Span) -> impl Future<Output = F::Output> {
+    #[cfg(feature = "tracing")]
+    return tracing::Instrument::instrument(future, span.clone());
+
+    #[cfg(not(feature = "tracing"))]
+    future
+}
+
+/// Get the storefront of a catalog path
+#[cfg(feature = "tracing")]
+fn storefront(path: &str) -> Option<&str> {
+    path.strip_prefix("/v1/catalog/")?.split('/').next()
+}
+
+/// Get the request headers with sensitive values redacted
+#[cfg(feature = "tracing")]
+pub(crate) fn redact_headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = match REDACTED_HEADERS.contains(&name.as_str()) || value.is_sensitive() {
+                true => String::from("[redacted]"),
+                false => value.to_str().unwrap_or("[binary]").to_string(),
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
@@ -0,0 +1,266 @@
+//! Http transport
+
+use crate::error::Error;
+use crate::request::context::RequestContext;
+use crate::ApiClient;
+use futures::future::BoxFuture;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Default Apple Music api base url
+pub const DEFAULT_BASE_URL: &str = "https://api.music.apple.com";
+
+/// Get the endpoint template of a path, replacing the storefront and ids with placeholders
+///
+/// For example `/v1/catalog/us/songs/1` becomes `/v1/catalog/{storefront}/songs/{id}`
+pub fn endpoint_template(path: &str) -> String {
+    let path = path.split_once('?').map(|(path, _)| path).unwrap_or(path);
+
+    path.split('/')
+        .enumerate()
+        .map(|(i, segment)| {
+            let is_storefront = i == 3 && path.starts_with("/v1/catalog/");
+            let is_id = i > 1 && segment.contains(|c: char| c.is_ascii_digit() || c == '.');
+
+            match (is_storefront, is_id) {
+                (true, _) => "{storefront}",
+                (_, true) => "{id}",
+                _ => segment,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A transport used by [`ApiClient`] to send http requests
+///
+/// Transports receive fully built requests, including authorization headers,
+/// and are expected to return the raw response without interpreting the status code
+pub trait Transport: Send + Sync {
+    /// Send a request
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>>;
+}
+
+/// Transport request
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    /// Http method
+    pub method: Method,
+    /// Absolute url, might already contain a query string
+    pub url: String,
+    /// Query parameters to append to the url
+    pub query: Vec<(String, String)>,
+    /// Request headers
+    pub headers: HeaderMap,
+    /// Request body
+    pub body: Option<Vec<u8>>,
+}
+
+impl TransportRequest {
+    /// Get the path of this request relative to `base_url`, without the query string
+    pub fn path(&self, base_url: &str) -> &str {
+        let url = self.url.strip_prefix(base_url).unwrap_or(&self.url);
+        url.split_once('?').map(|(path, _)| path).unwrap_or(url)
+    }
+
+    /// Get the query parameters of both the url and [`TransportRequest::query`], sorted
+    pub fn normalized_query(&self) -> Vec<(String, String)> {
+        let mut query = self
+            .url
+            .split_once('?')
+            .map(|(_, query)| query)
+            .into_iter()
+            .flat_map(|query| query.split('&'))
+            .filter(|e| !e.is_empty())
+            .map(|e| match e.split_once('=') {
+                Some((k, v)) => (k.to_string(), v.to_string()),
+                None => (e.to_string(), String::new()),
+            })
+            .chain(self.query.iter().cloned())
+            .collect::<Vec<_>>();
+        query.sort();
+        query
+    }
+
+    /// Get the first value of a query parameter, looking at both the url and [`TransportRequest::query`]
+    pub fn query_value(&self, key: &str) -> Option<&str> {
+        let url_query = self
+            .url
+            .split_once('?')
+            .map(|(_, query)| query)
+            .unwrap_or_default();
+
+        url_query
+            .split('&')
+            .filter_map(|e| e.split_once('='))
+            .chain(self.query.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+}
+
+/// Transport response
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    /// Http status
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Response body
+    pub body: Vec<u8>,
+    /// Set when the api answered `304 Not Modified` and this is a previously received response
+    pub not_modified: bool,
+}
+
+impl TransportResponse {
+    /// Create a new [`TransportResponse`] instance with a json body
+    pub fn from_json<T: Serialize + ?Sized>(status: StatusCode, json: &T) -> Result<Self, Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        Ok(TransportResponse {
+            status,
+            headers,
+            body: serde_json::to_vec(json)?,
+            not_modified: false,
+        })
+    }
+
+    /// Turn an unsuccessful response into an [`Error`]
+    pub fn error_for_status(self) -> Result<Self, Error> {
+        match self.status.is_success() {
+            true => Ok(self),
+            false => Err(Error::from_response(self)),
+        }
+    }
+
+    /// Deserialize the response body as json
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// [`Transport`] implementation backed by a [`reqwest::Client`]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a new [`ReqwestTransport`] instance
+    pub fn new(client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .query(&request.query)
+                .headers(request.headers);
+
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder.send().await?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?.to_vec();
+
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+                not_modified: false,
+            })
+        })
+    }
+}
+
+/// Api request builder
+pub struct RequestBuilder<'a> {
+    client: &'a ApiClient,
+    request: Result<TransportRequest, Error>,
+    bypass_cache: bool,
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// Create a new [`RequestBuilder`] instance
+    pub(crate) fn new(client: &'a ApiClient, request: TransportRequest) -> RequestBuilder<'a> {
+        RequestBuilder {
+            client,
+            request: Ok(request),
+            bypass_cache: false,
+        }
+    }
+
+    /// Append query parameters to this request
+    pub fn query<K: ToString, V: ToString>(mut self, query: &[(K, V)]) -> Self {
+        if let Ok(request) = &mut self.request {
+            request
+                .query
+                .extend(query.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        }
+        self
+    }
+
+    /// Append the query parameters and cache behavior of a request context to this request
+    pub(crate) fn context(self, context: &RequestContext) -> Self {
+        self.query(&context.query)
+            .bypass_cache(context.bypass_cache)
+    }
+
+    /// Bypass the client's response cache for this request
+    pub fn bypass_cache(mut self, bypass_cache: bool) -> Self {
+        self.bypass_cache = bypass_cache;
+        self
+    }
+
+    /// Set a header on this request
+    pub fn header(mut self, name: header::HeaderName, value: HeaderValue) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.headers.insert(name, value);
+        }
+        self
+    }
+
+    /// Set a json body on this request
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        if let Ok(request) = &mut self.request {
+            match serde_json::to_vec(json) {
+                Ok(body) => {
+                    request.headers.insert(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("application/json"),
+                    );
+                    request.body = Some(body);
+                }
+                Err(e) => self.request = Err(e.into()),
+            }
+        }
+        self
+    }
+
+    /// Build the request without sending it
+    pub fn build(self) -> Result<TransportRequest, Error> {
+        self.request
+    }
+
+    /// Send the request
+    pub async fn send(self) -> Result<TransportResponse, Error> {
+        let request = self.request?;
+        self.client
+            .execute_with_cache(request, !self.bypass_cache)
+            .await
+    }
+}
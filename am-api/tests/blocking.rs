@@ -0,0 +1,210 @@
+#![cfg(feature = "blocking")]
+
+use am_api::blocking;
+use am_api::error::Error;
+use am_api::resource::catalog::album::{Album, AlbumRelationshipType, AlbumViewType};
+use am_api::resource::catalog::search::{CatalogSearch, CatalogSearchType};
+use am_api::resource::library::song::LibrarySongGetRequestBuilder;
+use am_api::resource::library::LibraryAddResourceBuilder;
+use am_api::resource::rating::Rating;
+use am_api::resource::Resource;
+use common::{create_fake_client, json_response, FakeTransport};
+use reqwest::Method;
+use serde_json::json;
+
+mod common;
+
+fn create_transport() -> FakeTransport {
+    FakeTransport::new(|request| {
+        let path = request.path(am_api::transport::DEFAULT_BASE_URL);
+        match (&request.method, path) {
+            (&Method::GET, "/v1/catalog/us/albums/1") => json_response(
+                200,
+                json!({
+                    "data": [{
+                        "id": "1",
+                        "type": "albums",
+                        "relationships": {
+                            "tracks": {
+                                "href": "/v1/catalog/us/albums/1/tracks",
+                                "next": "/v1/catalog/us/albums/1/tracks?offset=1",
+                                "data": [{ "id": "10", "type": "songs" }]
+                            }
+                        },
+                        "views": {
+                            "other-versions": {
+                                "href": "/v1/catalog/us/albums/1/view/other-versions",
+                                "next": "/v1/catalog/us/albums/1/view/other-versions?offset=1",
+                                "attributes": { "title": "Other Versions" },
+                                "data": [{ "id": "2", "type": "albums" }]
+                            }
+                        }
+                    }]
+                }),
+            ),
+            (&Method::GET, "/v1/catalog/us/albums/1/tracks") => {
+                json_response(200, json!({ "data": [{ "id": "11", "type": "songs" }] }))
+            }
+            (&Method::GET, "/v1/catalog/us/albums/1/view/other-versions") => json_response(
+                200,
+                json!({
+                    "attributes": { "title": "Other Versions" },
+                    "data": [{ "id": "3", "type": "albums" }]
+                }),
+            ),
+            (&Method::GET, "/v1/catalog/us/albums") => {
+                let ids = request.query_value("ids").unwrap_or_default();
+                let data = ids
+                    .split(',')
+                    .map(|id| json!({ "id": id, "type": "albums" }))
+                    .collect::<Vec<_>>();
+                json_response(200, json!({ "data": data }))
+            }
+            (&Method::GET, "/v1/catalog/US/search") => json_response(
+                200,
+                json!({
+                    "results": {
+                        "albums": {
+                            "href": "/v1/catalog/us/search?types=albums",
+                            "data": [{ "id": "1", "type": "albums" }]
+                        }
+                    }
+                }),
+            ),
+            (&Method::GET, "/v1/me/library/songs") => {
+                let data = match request.query_value("offset") {
+                    Some("0") => json!([
+                        { "id": "i.1", "type": "library-songs" },
+                        { "id": "i.2", "type": "library-songs" }
+                    ]),
+                    Some("2") => json!([{ "id": "i.3", "type": "library-songs" }]),
+                    _ => json!([]),
+                };
+                json_response(200, json!({ "data": data }))
+            }
+            (&Method::POST, "/v1/me/library") => json_response(202, json!({ "data": [] })),
+            (&Method::PUT, "/v1/me/ratings/albums/1") => json_response(
+                200,
+                json!({
+                    "data": [{
+                        "id": "1",
+                        "type": "ratings",
+                        "attributes": { "rating": 1 },
+                        "relationships": {}
+                    }]
+                }),
+            ),
+            _ => json_response(404, json!({ "errors": [] })),
+        }
+    })
+}
+
+fn create_client(transport: FakeTransport) -> blocking::ApiClient {
+    blocking::ApiClient::new(create_fake_client(transport)).expect("failed to create api client")
+}
+
+#[test]
+fn blocking_fetches_resources() -> Result<(), Error> {
+    let client = create_client(create_transport());
+
+    let album = client
+        .block_on(Album::get().one(&client, "1"))?
+        .expect("album fetch returned none");
+    assert_eq!(album.header.id, "1");
+
+    let albums = client.block_on(Album::get().many(&client, &["1", "2", "3"], false))?;
+    let ids = albums
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["1", "2", "3"]);
+
+    let results = client.block_on(CatalogSearch::search().search(
+        &client,
+        &[CatalogSearchType::Albums],
+        "album",
+    ))?;
+    assert_eq!(results.albums.data.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn blocking_iterates_streams() -> Result<(), Error> {
+    let client = create_client(create_transport());
+
+    let songs = client
+        .iter(LibrarySongGetRequestBuilder::default().all(&client, 2, 0))
+        .map(|e| e.map(|e| e.header.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(songs, vec!["i.1", "i.2", "i.3"]);
+
+    let album = client
+        .block_on(
+            Album::get()
+                .include(AlbumRelationshipType::Tracks)
+                .view(AlbumViewType::OtherVersions)
+                .one(&client, "1"),
+        )?
+        .expect("album fetch returned none");
+
+    let tracks = album
+        .relationships
+        .tracks
+        .expect("album fetch didn't return any track relationships");
+    let tracks = client
+        .iter(tracks.iter(&client))
+        .map(|e| {
+            e.map(|e| match e {
+                Resource::Song { data } => data.header.id,
+                _ => panic!("unexpected resource type"),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(tracks, vec!["10", "11"]);
+
+    let other_versions = album
+        .views
+        .other_versions
+        .expect("album fetch didn't return any other versions view");
+    let other_versions = client
+        .iter(other_versions.iter(&client))
+        .map(|e| e.map(|e| e.header.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(other_versions, vec!["2", "3"]);
+
+    Ok(())
+}
+
+#[test]
+fn blocking_mutates_library_and_ratings() -> Result<(), Error> {
+    let transport = create_transport();
+    let client = create_client(transport.clone());
+
+    let album = client
+        .block_on(Album::get().one(&client, "1"))?
+        .expect("album fetch returned none");
+    let album: Resource = album.into();
+
+    let added = client.block_on(
+        LibraryAddResourceBuilder::new()
+            .add_resource(&album)?
+            .send(&client),
+    )?;
+    assert!(added.is_empty());
+
+    let rating = client
+        .block_on(Rating::add_rating().add_rating(&client, &album))?
+        .expect("rating returned none");
+    assert_eq!(rating.header.id, "1");
+
+    let methods = transport
+        .requests()
+        .into_iter()
+        .map(|e| e.method)
+        .collect::<Vec<_>>();
+    assert_eq!(methods, vec![Method::GET, Method::POST, Method::PUT]);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use am_api::cache::ResponseCache;
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::song::LibrarySongGetRequestBuilder;
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+
+fn create_transport() -> common::FakeTransport {
+    common::FakeTransport::new(|_| {
+        common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] }))
+    })
+}
+
+#[tokio::test]
+async fn cache_catalog_requests() -> Result<(), Error> {
+    let transport = create_transport();
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_cache(ResponseCache::default());
+
+    Song::get().one(&client, "1").await?;
+    Song::get().one(&client, "1").await?;
+    Song::get().many(&client, &["1", "2"], false).await?;
+    Song::get().many(&client, &["1", "2"], false).await?;
+
+    assert_eq!(transport.requests().len(), 2);
+
+    Song::get().bypass_cache().one(&client, "1").await?;
+
+    assert_eq!(transport.requests().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cache_skips_personalized_requests() -> Result<(), Error> {
+    let transport = create_transport();
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_cache(ResponseCache::default());
+
+    for _ in 0..2 {
+        LibrarySongGetRequestBuilder::default()
+            .one(&client, "i.1")
+            .await?;
+    }
+
+    assert_eq!(transport.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cache_route_ttl() -> Result<(), Error> {
+    let cache = ResponseCache::default()
+        .route_ttl("/v1/catalog/*/songs", Duration::ZERO)
+        .route_ttl("/v1/me/library", Duration::from_secs(60));
+
+    assert_eq!(cache.get_ttl("/v1/catalog/us/songs/1"), Duration::ZERO);
+    assert_eq!(
+        cache.get_ttl("/v1/catalog/us/albums/1"),
+        am_api::cache::DEFAULT_TTL
+    );
+    assert_eq!(cache.get_ttl("/v1/me/storefront"), Duration::ZERO);
+    assert_eq!(
+        cache.get_ttl("/v1/me/library/songs"),
+        Duration::from_secs(60)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn cache_expires_and_evicts() -> Result<(), Error> {
+    let transport = create_transport();
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_cache(ResponseCache::new(1).ttl(Duration::from_millis(100)));
+
+    Song::get().one(&client, "1").await?;
+    Song::get().one(&client, "2").await?;
+    Song::get().one(&client, "1").await?;
+    assert_eq!(transport.requests().len(), 3);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    Song::get().one(&client, "1").await?;
+    assert_eq!(transport.requests().len(), 4);
+    assert_eq!(client.get_cache().unwrap().len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use am_api::cassette::{Cassette, CassetteMode};
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::ApiClient;
+use common::{create_fake_client, json_response, FakeTransport};
+use reqwest::header::HeaderValue;
+use serde_json::json;
+use std::path::PathBuf;
+
+mod common;
+
+fn create_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "am-api-cassette-{}-{}.json",
+        name,
+        std::process::id()
+    ))
+}
+
+/// Transport echoing the media user token back, like a misbehaving proxy would
+fn create_transport() -> FakeTransport {
+    FakeTransport::new(|request| {
+        let token = request
+            .headers
+            .get("media-user-token")
+            .and_then(|e| e.to_str().ok())
+            .unwrap_or_default();
+        json_response(
+            200,
+            json!({ "data": [{ "id": "1", "type": "songs", "attributes": { "name": token } }] }),
+        )
+    })
+}
+
+fn create_replay_client(path: &PathBuf) -> Result<ApiClient, Error> {
+    let transport = FakeTransport::new(|request| panic!("unexpected request {}", request.url));
+    let mut client = create_fake_client(transport);
+    client.set_cassette(Cassette::replay(path)?);
+    Ok(client)
+}
+
+#[tokio::test]
+async fn cassette_records_and_replays() -> Result<(), Error> {
+    let path = create_path("replay");
+    let transport = create_transport();
+    let mut client = create_fake_client(transport.clone());
+    client.set_cassette(Cassette::record(&path));
+
+    let recorded = Song::get().one(&client, "1").await?;
+    assert_eq!(transport.requests().len(), 1);
+
+    let client = create_replay_client(&path)?;
+    let replayed = Song::get().one(&client, "1").await?;
+    assert_eq!(replayed.map(|e| e.header.id), recorded.map(|e| e.header.id));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn cassette_scrubs_tokens() -> Result<(), Error> {
+    let path = create_path("scrub");
+    let mut client = create_fake_client(create_transport());
+    client.set_cassette(Cassette::record(&path));
+
+    Song::get().one(&client, "1").await?;
+
+    let recording = std::fs::read_to_string(&path)?;
+    assert!(!recording.contains("developer-token"));
+    assert!(!recording.contains("media-user-token\""));
+    assert!(recording.contains("[redacted]"));
+    assert!(recording.contains("/v1/catalog/us/songs/1"));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn cassette_keeps_request_id_headers() -> Result<(), Error> {
+    let path = create_path("headers");
+    let transport = FakeTransport::new(|_| {
+        let mut response = json_response(200, json!({ "data": [] }));
+        response
+            .headers
+            .insert("set-cookie", HeaderValue::from_static("session=secret"));
+        response.headers.insert(
+            "x-apple-jingle-correlation-key",
+            HeaderValue::from_static("CORRELATIONKEY"),
+        );
+        response
+    });
+    let mut client = create_fake_client(transport);
+    client.set_cassette(Cassette::record(&path));
+
+    Song::get().one(&client, "1").await?;
+
+    let recording = std::fs::read_to_string(&path)?;
+    assert!(!recording.contains("session=secret"));
+    assert!(recording.contains("CORRELATIONKEY"));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn cassette_fails_on_unmatched_requests() -> Result<(), Error> {
+    let path = create_path("unmatched");
+    let mut client = create_fake_client(create_transport());
+    client.set_cassette(Cassette::record(&path));
+    Song::get().one(&client, "1").await?;
+
+    let cassette = Cassette::replay(&path)?;
+    assert_eq!(cassette.get_mode(), CassetteMode::Replay);
+
+    let client = create_replay_client(&path)?;
+    let result = Song::get().one(&client, "2").await;
+    assert!(matches!(result, Err(Error::CassetteMiss(request)) if request.contains("/songs/2")));
+
+    let result = Song::get()
+        .override_localization("en-GB")
+        .one(&client, "1")
+        .await;
+    assert!(matches!(result, Err(Error::CassetteMiss(_))));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::resource::history::History;
+use am_api::resource::rating::{Rating, RatingType};
+use am_api::token::StaticTokenProvider;
+use am_api::ApiClient;
+use futures::StreamExt;
+use serde_json::json;
+
+mod common;
+
+fn create_catalog_client(transport: common::FakeTransport) -> Result<ApiClient, Error> {
+    let mut client = common::create_fake_client(transport);
+    client.set_token_provider(StaticTokenProvider::new("developer-token", None)?);
+    Ok(client)
+}
+
+#[tokio::test]
+async fn catalog_only_fetch() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] }))
+    });
+    let client = create_catalog_client(transport.clone())?;
+
+    let song = Song::get().one(&client, "1").await?;
+
+    assert!(song.is_some());
+    let requests = transport.requests();
+    assert!(requests[0].headers.get("authorization").is_some());
+    assert!(requests[0].headers.get("media-user-token").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn catalog_only_personalized() -> Result<(), Error> {
+    let transport =
+        common::FakeTransport::new(|_| panic!("personalized requests should not be sent"));
+    let client = create_catalog_client(transport.clone())?;
+
+    let rating = Rating::get().one(&client, RatingType::Song, "1").await;
+    assert!(matches!(
+        rating,
+        Err(Error::MissingMediaUserToken(endpoint)) if endpoint == "/v1/me/ratings/songs/1"
+    ));
+
+    let history = History::get().heavy_rotation(&client, 10, 0).await;
+    let history = Box::pin(history).next().await;
+    assert!(matches!(
+        history,
+        Some(Err(Error::MissingMediaUserToken(_)))
+    ));
+
+    assert!(transport.requests().is_empty());
+
+    Ok(())
+}
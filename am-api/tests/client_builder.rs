@@ -0,0 +1,69 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::retry::RetryPolicy;
+use am_api::ApiClient;
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+
+#[tokio::test]
+async fn build_client() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] }))
+    });
+
+    let client = ApiClient::builder()
+        .developer_token("developer-token")
+        .media_user_token("media-user-token")
+        .transport(transport.clone())
+        .base_url("http://localhost:1234")
+        .storefront_country(
+            celes::Country::the_united_kingdom_of_great_britain_and_northern_ireland(),
+        )
+        .localization("en-GB")
+        .retry_policy(RetryPolicy::none())
+        .build()?;
+
+    assert_eq!(client.get_localization(), "en-GB");
+    assert_eq!(client.get_retry_policy(), &RetryPolicy::none());
+
+    Song::get().one(&client, "1").await?;
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url,
+        "http://localhost:1234/v1/catalog/gb/songs/1"
+    );
+    assert_eq!(requests[0].query_value("l"), Some("en-GB"));
+
+    Ok(())
+}
+
+#[test]
+fn build_client_reqwest_options() -> Result<(), Error> {
+    ApiClient::builder()
+        .developer_token("developer-token")
+        .timeout(Duration::from_secs(10))
+        .connect_timeout(Duration::from_secs(2))
+        .user_agent("am-api-tests")
+        .proxy(reqwest::Proxy::all("http://localhost:3128")?)
+        .pool_max_idle_per_host(4)
+        .pool_idle_timeout(Duration::from_secs(30))
+        .build()?;
+
+    ApiClient::builder()
+        .developer_token("developer-token")
+        .reqwest_client(reqwest::Client::new())
+        .build()?;
+
+    Ok(())
+}
+
+#[test]
+fn build_client_without_developer_token() {
+    let result = ApiClient::builder()
+        .media_user_token("media-user-token")
+        .build();
+    assert!(matches!(result, Err(Error::MissingDeveloperToken)));
+}
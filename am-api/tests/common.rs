@@ -1,5 +1,9 @@
+use am_api::error::Error;
+use am_api::transport::{Transport, TransportRequest, TransportResponse};
 use am_api::ApiClient;
+use futures::future::BoxFuture;
 use std::env;
+use std::sync::{Arc, Mutex};
 
 #[allow(dead_code)]
 pub fn create_client() -> ApiClient {
@@ -14,3 +18,51 @@ pub fn create_client() -> ApiClient {
     )
     .expect("failed to create api client")
 }
+
+/// In-process transport answering requests with a handler
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct FakeTransport {
+    handler: Arc<dyn Fn(&TransportRequest) -> TransportResponse + Send + Sync>,
+    pub requests: Arc<Mutex<Vec<TransportRequest>>>,
+}
+
+#[allow(dead_code)]
+impl FakeTransport {
+    pub fn new(
+        handler: impl Fn(&TransportRequest) -> TransportResponse + Send + Sync + 'static,
+    ) -> FakeTransport {
+        FakeTransport {
+            handler: Arc::new(handler),
+            requests: Arc::default(),
+        }
+    }
+
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        let response = (self.handler)(&request);
+        self.requests.lock().unwrap().push(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[allow(dead_code)]
+pub fn create_fake_client(transport: FakeTransport) -> ApiClient {
+    ApiClient::with_transport(
+        "developer-token",
+        "media-user-token",
+        celes::Country::the_united_states_of_america(),
+        transport,
+    )
+    .expect("failed to create api client")
+}
+
+#[allow(dead_code)]
+pub fn json_response(status: u16, json: serde_json::Value) -> TransportResponse {
+    TransportResponse::from_json(status.try_into().unwrap(), &json).unwrap()
+}
@@ -0,0 +1,153 @@
+use am_api::cache::conditional::ConditionalCache;
+use am_api::error::Error;
+use am_api::request::try_resource_response;
+use am_api::resource::library::playlist::LibraryPlaylist;
+use am_api::token::StaticTokenProvider;
+use am_api::transport::TransportResponse;
+use futures::TryStreamExt;
+use serde_json::json;
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+fn create_transport(version: Arc<AtomicUsize>) -> common::FakeTransport {
+    common::FakeTransport::new(move |request| {
+        let etag = format!("\"{}\"", version.load(Ordering::SeqCst));
+
+        if request
+            .headers
+            .get("if-none-match")
+            .is_some_and(|e| *e == etag)
+        {
+            return TransportResponse {
+                status: 304.try_into().unwrap(),
+                headers: Default::default(),
+                body: Vec::new(),
+                not_modified: false,
+            };
+        }
+
+        let mut response = common::json_response(
+            200,
+            json!({ "data": [{ "id": etag, "type": "library-playlists" }] }),
+        );
+        response.headers.insert("etag", etag.try_into().unwrap());
+        response
+    })
+}
+
+#[tokio::test]
+async fn conditional_not_modified() -> Result<(), Error> {
+    let version = Arc::new(AtomicUsize::new(1));
+    let transport = create_transport(version.clone());
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_conditional_cache(ConditionalCache::default());
+
+    let first = try_resource_response::<LibraryPlaylist>(
+        client.get("/v1/me/library/playlists/p.1").send().await?,
+    )?;
+    assert!(!first.not_modified);
+
+    let second = try_resource_response::<LibraryPlaylist>(
+        client.get("/v1/me/library/playlists/p.1").send().await?,
+    )?;
+    assert!(second.not_modified);
+    assert_eq!(first.data, second.data);
+
+    version.store(2, Ordering::SeqCst);
+
+    let third = try_resource_response::<LibraryPlaylist>(
+        client.get("/v1/me/library/playlists/p.1").send().await?,
+    )?;
+    assert!(!third.not_modified);
+    assert_eq!(third.data[0].header.id, "\"2\"");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].headers.get("if-none-match").is_none());
+    assert_eq!(requests[1].headers["if-none-match"], "\"1\"");
+    assert_eq!(requests[2].headers["if-none-match"], "\"1\"");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn conditional_transparent_for_builders() -> Result<(), Error> {
+    let transport = create_transport(Arc::new(AtomicUsize::new(1)));
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_conditional_cache(ConditionalCache::default());
+
+    for i in 0..2 {
+        let response = LibraryPlaylist::get().one_response(&client, "p.1").await?;
+        assert_eq!(response.not_modified, i == 1);
+
+        let playlist = response
+            .data
+            .into_iter()
+            .next()
+            .expect("playlist fetch returned none");
+        assert_eq!(playlist.header.id, "\"1\"");
+    }
+
+    assert_eq!(transport.requests().len(), 2);
+    assert_eq!(client.get_conditional_cache().unwrap().len(), 1);
+
+    for i in 0..2 {
+        let playlists = LibraryPlaylist::get().many(&client, &["p.1"]).await?;
+        assert_eq!(playlists.not_modified, i == 1);
+
+        let page = pin!(LibraryPlaylist::get().all_pages(&client, 1, 0))
+            .try_next()
+            .await?
+            .expect("playlist pages returned none");
+        assert_eq!(page.not_modified, i == 1);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn conditional_keeps_users_apart() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        if request.headers.contains_key("if-none-match") {
+            return TransportResponse {
+                status: 304.try_into().unwrap(),
+                headers: Default::default(),
+                body: Vec::new(),
+                not_modified: false,
+            };
+        }
+
+        let token = request.headers["media-user-token"].to_str().unwrap();
+        let mut response = common::json_response(
+            200,
+            json!({ "data": [{ "id": token, "type": "library-playlists" }] }),
+        );
+        response.headers.insert("etag", "\"1\"".try_into().unwrap());
+        response
+    });
+    let mut first = common::create_fake_client(transport.clone());
+    first.set_conditional_cache(ConditionalCache::default());
+    let mut second = first.clone();
+    second.set_token_provider(StaticTokenProvider::new(
+        "developer-token",
+        Some("second-user"),
+    )?);
+
+    let playlist = LibraryPlaylist::get().one(&first, "p.1").await?;
+    assert_eq!(playlist.unwrap().header.id, "media-user-token");
+
+    let response = LibraryPlaylist::get().one_response(&second, "p.1").await?;
+    assert!(!response.not_modified);
+    assert_eq!(response.data[0].header.id, "second-user");
+
+    let response = LibraryPlaylist::get().one_response(&first, "p.1").await?;
+    assert!(response.not_modified);
+    assert_eq!(response.data[0].header.id, "media-user-token");
+
+    assert_eq!(first.get_conditional_cache().unwrap().len(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use am_api::error::Error;
+use am_api::request::AttachContext;
+use am_api::resource::catalog::album::Album;
+use futures::TryStreamExt;
+use serde_json::json;
+
+mod common;
+
+fn saved_album() -> Album {
+    serde_json::from_value(json!({
+        "id": "1",
+        "type": "albums",
+        "relationships": {
+            "tracks": {
+                "href": "/v1/catalog/us/albums/1/tracks",
+                "next": "/v1/catalog/us/albums/1/tracks?offset=1",
+                "data": [{ "id": "1", "type": "songs" }]
+            }
+        }
+    }))
+    .expect("failed to deserialize album")
+}
+
+#[tokio::test]
+async fn iter_without_context_fails() {
+    let transport =
+        common::FakeTransport::new(|_| common::json_response(200, json!({ "data": [] })));
+    let client = common::create_fake_client(transport.clone());
+
+    let album = saved_album();
+    let tracks = album.relationships.tracks.expect("album has no tracks");
+    let result = tracks.iter(&client).try_collect::<Vec<_>>().await;
+
+    assert!(matches!(result, Err(Error::MissingContext)));
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+async fn iter_with_attached_context() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(200, json!({ "data": [{ "id": "2", "type": "songs" }] }))
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let mut album = saved_album();
+    album.attach_context(celes::Country::germany(), "de-DE");
+    let tracks = album.relationships.tracks.expect("album has no tracks");
+    let tracks = tracks.iter(&client).try_collect::<Vec<_>>().await?;
+
+    assert_eq!(tracks.len(), 2);
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].query_value("offset"), Some("1"));
+    assert_eq!(requests[0].query_value("l"), Some("de-DE"));
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use am_api::cache::disk::DiskCache;
+use am_api::error::Error;
+use am_api::resource::catalog::album::{Album, AlbumRelationshipType};
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::song::LibrarySongGetRequestBuilder;
+use am_api::resource::Resource;
+use am_api::ApiClient;
+use futures::StreamExt;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+fn create_directory(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("am-api-{}-{}", name, std::process::id()))
+}
+
+fn create_online_transport() -> common::FakeTransport {
+    common::FakeTransport::new(
+        |request| match request.path(am_api::transport::DEFAULT_BASE_URL) {
+            "/v1/catalog/us/albums/1" => common::json_response(
+                200,
+                json!({
+                    "data": [{
+                        "id": "1",
+                        "type": "albums",
+                        "relationships": {
+                            "tracks": {
+                                "href": "/v1/catalog/us/albums/1/tracks",
+                                "next": "/v1/catalog/us/albums/1/tracks?offset=1",
+                                "data": [{ "id": "10", "type": "songs" }]
+                            }
+                        }
+                    }]
+                }),
+            ),
+            "/v1/catalog/us/albums/1/tracks" => {
+                common::json_response(200, json!({ "data": [{ "id": "11", "type": "songs" }] }))
+            }
+            _ => common::json_response(
+                200,
+                json!({ "data": [{ "id": "i.1", "type": "library-songs" }] }),
+            ),
+        },
+    )
+}
+
+fn create_offline_client(directory: &Path) -> ApiClient {
+    let transport =
+        common::FakeTransport::new(|request| panic!("unexpected request {}", request.url));
+    let mut client = common::create_fake_client(transport);
+    client.set_disk_cache(DiskCache::new(directory));
+    client.set_offline(true);
+    client
+}
+
+async fn fetch_track_ids(client: &ApiClient) -> Result<Vec<String>, Error> {
+    let album = Album::get()
+        .include(AlbumRelationshipType::Tracks)
+        .one(client, "1")
+        .await?
+        .expect("album fetch returned none");
+
+    album
+        .relationships
+        .tracks
+        .expect("album fetch didn't return any track relationships")
+        .iter(client)
+        .map(|e| {
+            e.map(|e| match e {
+                Resource::Song { data } => data.header.id,
+                _ => panic!("unexpected resource type"),
+            })
+        })
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+#[tokio::test]
+async fn disk_cache_offline() -> Result<(), Error> {
+    let directory = create_directory("offline");
+
+    let transport = create_online_transport();
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_disk_cache(DiskCache::new(&directory));
+
+    assert_eq!(fetch_track_ids(&client).await?, ["10", "11"]);
+    assert_eq!(fetch_track_ids(&client).await?, ["10", "11"]);
+    assert_eq!(transport.requests().len(), 2);
+
+    let client = create_offline_client(&directory);
+    assert_eq!(fetch_track_ids(&client).await?, ["10", "11"]);
+
+    let result = Song::get().one(&client, "1").await;
+    assert!(matches!(result, Err(Error::CacheMiss(path)) if path == "/v1/catalog/us/songs/1"));
+
+    client.get_disk_cache().unwrap().clear().await?;
+    assert!(matches!(
+        fetch_track_ids(&client).await,
+        Err(Error::CacheMiss(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn disk_cache_skips_personalized_requests() -> Result<(), Error> {
+    let directory = create_directory("personalized");
+
+    let mut client = common::create_fake_client(create_online_transport());
+    client.set_disk_cache(DiskCache::new(&directory));
+
+    LibrarySongGetRequestBuilder::default()
+        .one(&client, "i.1")
+        .await?;
+
+    let client = create_offline_client(&directory);
+    let result = LibrarySongGetRequestBuilder::default()
+        .one(&client, "i.1")
+        .await;
+    assert!(matches!(result, Err(Error::CacheMiss(_))));
+
+    client.get_disk_cache().unwrap().clear().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn disk_cache_write_failure_returns_response() -> Result<(), Error> {
+    // a directory below a regular file can never be created
+    let file = create_directory("unwritable");
+    std::fs::write(&file, "")?;
+    let directory = file.join("cache");
+
+    let transport = create_online_transport();
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_disk_cache(DiskCache::new(&directory));
+
+    assert_eq!(fetch_track_ids(&client).await?, ["10", "11"]);
+    assert_eq!(fetch_track_ids(&client).await?, ["10", "11"]);
+    assert_eq!(transport.requests().len(), 4);
+    assert!(!directory.exists());
+
+    std::fs::remove_file(&file)?;
+
+    Ok(())
+}
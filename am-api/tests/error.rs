@@ -0,0 +1,99 @@
+use am_api::error::Error;
+use am_api::resource::catalog::search::{CatalogSearch, CatalogSearchType};
+use am_api::resource::catalog::song::Song;
+use am_api::resource::rating::Rating;
+use am_api::resource::Resource;
+use am_api::retry::RetryPolicy;
+use am_api::transport::TransportResponse;
+use am_api::ApiClient;
+use common::{create_fake_client, json_response, FakeTransport};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+
+fn create_client(response: impl Fn() -> TransportResponse + Send + Sync + 'static) -> ApiClient {
+    let mut client = create_fake_client(FakeTransport::new(move |_| response()));
+    client.set_retry_policy(RetryPolicy::none());
+    client
+}
+
+fn html_response(status: StatusCode) -> TransportResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    headers.insert("x-request-id", HeaderValue::from_static("request-id"));
+
+    TransportResponse {
+        status,
+        headers,
+        body: b"<html>Unauthorized</html>".to_vec(),
+        not_modified: false,
+    }
+}
+
+#[tokio::test]
+async fn error_keeps_status_of_non_json_bodies() {
+    let client = create_client(|| html_response(StatusCode::UNAUTHORIZED));
+
+    let result = Song::get().one(&client, "1").await;
+
+    let Err(Error::Unauthorized(error)) = result else {
+        panic!("expected an unauthorized error, got {result:?}");
+    };
+    assert_eq!(error.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error.request_id.as_deref(), Some("request-id"));
+    assert_eq!(error.body, "<html>Unauthorized</html>");
+    assert!(error.response.is_none());
+}
+
+#[tokio::test]
+async fn error_rate_limited_is_retryable() {
+    let client = create_client(|| {
+        let mut response = json_response(429, json!({ "errors": [] }));
+        response
+            .headers
+            .insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        response
+    });
+
+    let result = Song::get().one(&client, "1").await;
+
+    let Err(error @ Error::RateLimited { retry_after, .. }) = &result else {
+        panic!("expected a rate limited error, got {result:?}");
+    };
+    assert_eq!(*retry_after, Some(Duration::from_secs(3)));
+    assert!(error.is_retryable());
+    assert_eq!(
+        error.api_error().map(|e| e.status),
+        Some(StatusCode::TOO_MANY_REQUESTS)
+    );
+}
+
+#[tokio::test]
+async fn error_classifies_statuses() {
+    let client = create_client(|| json_response(503, json!({ "errors": [] })));
+    let result = Song::get().one(&client, "1").await;
+    assert!(matches!(&result, Err(Error::ServerError(_))));
+    assert!(result.unwrap_err().is_retryable());
+
+    let client = create_client(|| json_response(418, json!({})));
+    let result = Song::get().one(&client, "1").await;
+    assert!(matches!(&result, Err(Error::Unexpected(_))));
+    assert!(!result.unwrap_err().is_retryable());
+}
+
+#[tokio::test]
+async fn error_shared_by_hand_rolled_branches() {
+    let client = create_client(|| html_response(StatusCode::NOT_FOUND));
+    let result = CatalogSearch::search()
+        .search(&client, &[CatalogSearchType::Songs], "term")
+        .await;
+    assert!(matches!(result, Err(Error::NotFound(_))));
+
+    let client = create_client(|| html_response(StatusCode::FORBIDDEN));
+    let song: Resource = Song::default().into();
+    let result = Rating::remove_rating().remove_rating(&client, &song).await;
+    assert!(matches!(result, Err(Error::Forbidden(_))));
+}
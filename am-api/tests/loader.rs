@@ -0,0 +1,132 @@
+use am_api::error::Error;
+use am_api::request::loader::Loader;
+use am_api::resource::catalog::album::{Album, AlbumRelationshipType};
+use am_api::transport::{Transport, TransportRequest, TransportResponse};
+use am_api::ApiClient;
+use futures::future::{self, BoxFuture};
+use futures::FutureExt;
+use serde_json::json;
+use std::time::Duration;
+
+mod common;
+
+fn create_transport() -> common::FakeTransport {
+    common::FakeTransport::new(|request| {
+        let data = request
+            .query_value("ids")
+            .unwrap()
+            .split(',')
+            .filter(|id| *id != "missing")
+            .map(|id| json!({ "id": id, "type": "albums" }))
+            .collect::<Vec<_>>();
+        common::json_response(200, json!({ "data": data }))
+    })
+}
+
+#[tokio::test]
+async fn loader_batches_lookups() -> Result<(), Error> {
+    let transport = create_transport();
+    let client = common::create_fake_client(transport.clone());
+    let loader = Loader::new(&client, || {
+        Album::get().include(AlbumRelationshipType::Tracks)
+    });
+
+    let (a, b, c, missing) = tokio::join!(
+        loader.load("1"),
+        loader.load("2"),
+        loader.load("2"),
+        loader.load("missing")
+    );
+
+    assert_eq!(a?.unwrap().header.id, "1");
+    assert_eq!(b?.unwrap().header.id, "2");
+    assert_eq!(c?.unwrap().header.id, "2");
+    assert!(missing?.is_none());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].query_value("ids"), Some("1,2,missing"));
+    assert_eq!(requests[0].query_value("include[albums]"), Some("tracks"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn loader_batches_per_storefront() -> Result<(), Error> {
+    let transport = create_transport();
+    let client = common::create_fake_client(transport.clone());
+    let loader = Loader::new(&client, Album::get);
+
+    let (a, b) = tokio::join!(
+        loader.load("1"),
+        loader.load_from(
+            celes::Country::the_united_kingdom_of_great_britain_and_northern_ireland(),
+            "2"
+        )
+    );
+    a?;
+    b?;
+
+    let mut paths = transport
+        .requests()
+        .iter()
+        .map(|e| e.path(client.get_base_url()).to_string())
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(paths, ["/v1/catalog/gb/albums", "/v1/catalog/us/albums"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn loader_keeps_ids_differing_in_case_apart() -> Result<(), Error> {
+    let transport = create_transport();
+    let client = common::create_fake_client(transport.clone());
+    let loader = Loader::new(&client, Album::get);
+
+    let (a, b) = tokio::join!(loader.load("i.AbC"), loader.load("i.aBc"));
+
+    assert_eq!(a?.unwrap().header.id, "i.AbC");
+    assert_eq!(b?.unwrap().header.id, "i.aBc");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].query_value("ids"), Some("i.AbC,i.aBc"));
+
+    Ok(())
+}
+
+/// Transport that never answers
+struct PendingTransport;
+
+impl Transport for PendingTransport {
+    fn send(&self, _: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(future::pending())
+    }
+}
+
+#[test]
+fn loader_reports_cancelled_batches() {
+    let client = ApiClient::builder()
+        .developer_token("developer-token")
+        .media_user_token("media-user-token")
+        .transport(PendingTransport)
+        .build()
+        .unwrap();
+    let loader = Loader::new(&client, Album::get);
+
+    // the batch is dropped while in flight together with the runtime of its dispatch task
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let mut load = Box::pin(loader.load("1"));
+    runtime.block_on(async {
+        assert!(load.as_mut().now_or_never().is_none());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    });
+    drop(runtime);
+
+    let result = futures::executor::block_on(load);
+    assert!(matches!(result, Err(Error::BatchCancelled)));
+}
@@ -0,0 +1,419 @@
+use am_api::error::Error;
+use am_api::resource::catalog::album::Album;
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::LibraryAddResourceBuilder;
+use am_api::resource::storefront::Storefront;
+use am_api::resource::ResourceHeader;
+use am_api::transport::{Transport, TransportRequest, TransportResponse};
+use am_api::ApiClient;
+use futures::future::BoxFuture;
+use futures::StreamExt;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod common;
+
+/// Transport echoing requested ids, later chunks answer faster than earlier ones
+#[derive(Clone, Default)]
+struct ChunkTransport {
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+    chunks: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Transport for ChunkTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            let ids = request
+                .query_value("ids")
+                .unwrap()
+                .split(',')
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            self.chunks.lock().unwrap().push(ids.len());
+
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+            let first = ids[0].parse::<u64>().unwrap();
+            tokio::time::sleep(Duration::from_millis(50u64.saturating_sub(first / 10))).await;
+
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let data = ids
+                .iter()
+                .map(|id| json!({ "id": id, "type": "songs" }))
+                .collect::<Vec<_>>();
+            Ok(common::json_response(200, json!({ "data": data })))
+        })
+    }
+}
+
+fn create_client(transport: ChunkTransport) -> ApiClient {
+    ApiClient::builder()
+        .developer_token("developer-token")
+        .media_user_token("media-user-token")
+        .transport(transport)
+        .build()
+        .expect("failed to create api client")
+}
+
+#[tokio::test]
+async fn many_chunks_ids_in_order() -> Result<(), Error> {
+    let transport = ChunkTransport::default();
+    let client = create_client(transport.clone());
+
+    let ids = (0..650).map(|e| e.to_string()).collect::<Vec<_>>();
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let songs = Song::get()
+        .concurrency(2)
+        .many(&client, &ids, false)
+        .await?;
+
+    assert!(songs.is_complete());
+    let song_ids = songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(song_ids, ids);
+
+    assert_eq!(*transport.chunks.lock().unwrap(), [300, 300, 50]);
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_stream_chunks_ids() -> Result<(), Error> {
+    let transport = ChunkTransport::default();
+    let client = create_client(transport.clone());
+
+    let ids = (0..250).map(|e| e.to_string()).collect::<Vec<_>>();
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let albums = Album::get()
+        .many_stream(&client, &ids, false)
+        .map(|e| e.map(|e| e.header.id))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(albums, ids);
+    assert_eq!(*transport.chunks.lock().unwrap(), [100, 100, 50]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn storefront_many_chunks_ids() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let data = request
+            .query_value("ids")
+            .unwrap()
+            .split(',')
+            .map(|id| json!({ "id": id, "type": "storefronts" }))
+            .collect::<Vec<_>>();
+        common::json_response(200, json!({ "data": data }))
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let countries = celes::Country::get_countries();
+    let storefronts = Storefront::get().many(&client, &countries).await?;
+
+    assert!(storefronts.is_complete());
+    assert_eq!(storefronts.data.len(), countries.len());
+
+    let chunks = transport
+        .requests()
+        .iter()
+        .map(|e| e.query_value("ids").unwrap().split(',').count())
+        .collect::<Vec<_>>();
+    assert_eq!(chunks, [100, 100, 50]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn library_add_chunks_ids() -> Result<(), Error> {
+    let transport =
+        common::FakeTransport::new(|_| common::json_response(202, json!({ "data": [] })));
+    let client = common::create_fake_client(transport.clone());
+
+    let mut builder = LibraryAddResourceBuilder::new();
+    for id in 0..150 {
+        let song = Song {
+            header: ResourceHeader {
+                id: id.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        builder = builder.add_resource(&song.into())?;
+    }
+    let album = Album {
+        header: ResourceHeader {
+            id: String::from("1"),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    builder = builder.add_resource(&album.into())?;
+
+    builder.send(&client).await?;
+
+    let mut requests = transport
+        .requests()
+        .iter()
+        .map(|e| {
+            (
+                e.query_value("ids[songs]").unwrap().split(',').count(),
+                e.query_value("ids[albums]").is_some(),
+            )
+        })
+        .collect::<Vec<_>>();
+    requests.sort();
+    assert_eq!(requests, [(50, false), (100, true)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_reports_unresolved_ids() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            200,
+            json!({
+                "data": [{ "id": "1", "type": "songs" }],
+                "errors": [{
+                    "id": "error-id",
+                    "title": "Resource Not Found",
+                    "detail": "Resource with requested id was not found: 3",
+                    "status": "404",
+                    "code": "40400"
+                }]
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let songs = Song::get().many(&client, &["1", "2", "3"], false).await?;
+
+    assert!(!songs.is_complete());
+    assert_eq!(songs.data.len(), 1);
+    assert_eq!(songs.unresolved_ids(), vec!["2", "3"]);
+    assert_eq!(songs.unresolved[0].error, None);
+    assert_eq!(
+        songs.unresolved[1].error.as_ref().map(|e| e.code.as_str()),
+        Some("40400")
+    );
+    assert_eq!(songs.errors.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_matches_errors_by_whole_id() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            200,
+            json!({
+                "data": [],
+                "errors": [{
+                    "id": "error-id",
+                    "title": "Resource Not Found",
+                    "detail": "Resource with requested id was not found: 12.",
+                    "status": "404",
+                    "code": "40400"
+                }]
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let songs = Song::get().many(&client, &["1", "12"], false).await?;
+
+    assert_eq!(songs.unresolved_ids(), vec!["1", "12"]);
+    assert_eq!(songs.unresolved[0].error, None);
+    assert_eq!(
+        songs.unresolved[1].error.as_ref().map(|e| e.code.as_str()),
+        Some("40400")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_reports_rejected_chunks() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let ids = request.query_value("ids").unwrap();
+        if ids.contains("bad") {
+            return common::json_response(
+                400,
+                json!({
+                    "errors": [{
+                        "id": "error-id",
+                        "title": "Invalid Parameter Value",
+                        "detail": "One or more of the ids is invalid",
+                        "status": "400",
+                        "code": "40005",
+                        "source": { "parameter": "ids" }
+                    }]
+                }),
+            );
+        }
+
+        let data = ids
+            .split(',')
+            .map(|id| json!({ "id": id, "type": "albums" }))
+            .collect::<Vec<_>>();
+        common::json_response(200, json!({ "data": data }))
+    });
+    let client = common::create_fake_client(transport);
+
+    let ids = (0..101)
+        .map(|e| e.to_string())
+        .chain([String::from("bad")])
+        .collect::<Vec<_>>();
+    let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let albums = Album::get().many(&client, &ids, false).await?;
+
+    assert_eq!(albums.data.len(), 100);
+    assert_eq!(albums.unresolved_ids(), vec!["100", "bad"]);
+    assert!(albums
+        .unresolved
+        .iter()
+        .all(|e| e.error.as_ref().is_some_and(|e| e.code == "40005")));
+    assert_eq!(albums.errors.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_fails_on_rejected_parameters() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            400,
+            json!({
+                "errors": [{
+                    "id": "error-id",
+                    "title": "Invalid Parameter Value",
+                    "detail": "Invalid relationship name 'bad'",
+                    "status": "400",
+                    "code": "40005",
+                    "source": { "parameter": "include" }
+                }]
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let result = Album::get().many(&client, &["1", "2"], false).await;
+
+    let Err(Error::Unexpected(error)) = result else {
+        panic!("expected an unexpected response error, got {result:?}");
+    };
+    assert_eq!(error.status, 400);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_resolves_isrc_filters() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            200,
+            json!({
+                "data": [{ "id": "1", "type": "songs" }],
+                "meta": {
+                    "filters": {
+                        "isrc": {
+                            "USRC17607839": [{ "id": "1", "type": "songs" }],
+                            "GBAYE0000000": []
+                        }
+                    }
+                }
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let songs = Song::get()
+        .many(
+            &client,
+            &["usrc17607839", "GBAYE0000000", "USUM00000000"],
+            true,
+        )
+        .await?;
+
+    assert_eq!(songs.data.len(), 1);
+    assert_eq!(songs.unresolved_ids(), vec!["GBAYE0000000", "USUM00000000"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn many_keeps_order_of_ids() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        if request.query_value("filter[isrc]").is_some() {
+            return common::json_response(
+                200,
+                json!({
+                    "data": [
+                        { "id": "2", "type": "songs" },
+                        { "id": "1", "type": "songs" }
+                    ],
+                    "meta": {
+                        "filters": {
+                            "isrc": {
+                                "GBAYE0000000": [{ "id": "2", "type": "songs" }],
+                                "USRC17607839": [{ "id": "1", "type": "songs" }]
+                            }
+                        }
+                    }
+                }),
+            );
+        }
+
+        common::json_response(
+            200,
+            json!({
+                "data": [
+                    { "id": "i.aBc", "type": "songs" },
+                    { "id": "c", "type": "songs" },
+                    { "id": "i.AbC", "type": "songs" }
+                ]
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let songs = Song::get()
+        .many(&client, &["i.AbC", "b", "i.aBc", "C"], false)
+        .await?;
+    let song_ids = songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(song_ids, ["i.AbC", "i.aBc", "c"]);
+    assert_eq!(songs.unresolved_ids(), vec!["b", "C"]);
+
+    let songs = Song::get()
+        .many(&client, &["USRC17607839", "GBAYE0000000"], true)
+        .await?;
+    assert!(songs.is_complete());
+    let song_ids = songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(song_ids, ["1", "2"]);
+
+    Ok(())
+}
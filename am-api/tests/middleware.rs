@@ -0,0 +1,140 @@
+use am_api::error::Error;
+use am_api::middleware::{Middleware, Next};
+use am_api::resource::catalog::song::Song;
+use am_api::transport::{TransportRequest, TransportResponse};
+use common::{create_fake_client, json_response, FakeTransport};
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderName, HeaderValue};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+fn song_transport() -> FakeTransport {
+    FakeTransport::new(|request| {
+        let id = request.url.rsplit('/').next().unwrap_or_default();
+        json_response(200, json!({ "data": [{ "id": id, "type": "songs" }] }))
+    })
+}
+
+/// Middleware setting a header and recording the order it ran in
+struct Header {
+    name: &'static str,
+    order: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Middleware for Header {
+    fn handle<'a>(
+        &'a self,
+        mut request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        self.order.lock().unwrap().push(self.name);
+        request.headers.insert(
+            HeaderName::from_static(self.name),
+            HeaderValue::from_static("1"),
+        );
+        next.run(request)
+    }
+}
+
+/// Middleware answering song requests without calling the transport
+struct ShortCircuit;
+
+impl Middleware for ShortCircuit {
+    fn handle<'a>(
+        &'a self,
+        request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            match request.url.contains("/songs/") {
+                true => Ok(json_response(
+                    200,
+                    json!({ "data": [{ "id": "stub", "type": "songs" }] }),
+                )),
+                false => next.run(request).await,
+            }
+        })
+    }
+}
+
+/// Middleware rewriting the storefront of requests and the ids of responses
+struct Rewrite;
+
+impl Middleware for Rewrite {
+    fn handle<'a>(
+        &'a self,
+        mut request: TransportRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<TransportResponse, Error>> {
+        request.url = request.url.replace("/catalog/us/", "/catalog/gb/");
+        Box::pin(async move {
+            let mut response = next.run(request).await?;
+            response.body = String::from_utf8_lossy(&response.body)
+                .replace("\"1\"", "\"rewritten\"")
+                .into_bytes();
+            Ok(response)
+        })
+    }
+}
+
+#[tokio::test]
+async fn middleware_runs_in_order() -> Result<(), Error> {
+    let transport = song_transport();
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let mut client = create_fake_client(transport.clone());
+    client.add_middleware(Header {
+        name: "x-first",
+        order: order.clone(),
+    });
+    client.add_middleware(Header {
+        name: "x-second",
+        order: order.clone(),
+    });
+
+    Song::get().one(&client, "1").await?;
+
+    assert_eq!(*order.lock().unwrap(), vec!["x-first", "x-second"]);
+    let request = &transport.requests()[0];
+    assert!(request.headers.contains_key("x-first"));
+    assert!(request.headers.contains_key("x-second"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn middleware_short_circuits_requests() -> Result<(), Error> {
+    let transport = song_transport();
+    let mut client = create_fake_client(transport.clone());
+    client.add_middleware(ShortCircuit);
+
+    let song = Song::get()
+        .one(&client, "1")
+        .await?
+        .expect("song fetch returned none");
+
+    assert_eq!(song.header.id, "stub");
+    assert!(transport.requests().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn middleware_rewrites_requests_and_responses() -> Result<(), Error> {
+    let transport = song_transport();
+    let mut client = create_fake_client(transport.clone());
+    client.add_middleware(Rewrite);
+
+    let song = Song::get()
+        .one(&client, "1")
+        .await?
+        .expect("song fetch returned none");
+
+    assert_eq!(song.header.id, "rewritten");
+    assert!(transport.requests()[0]
+        .url
+        .contains("/v1/catalog/gb/songs/1"));
+
+    Ok(())
+}
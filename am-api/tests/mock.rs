@@ -0,0 +1,414 @@
+#![cfg(feature = "mock")]
+
+use am_api::error::Error;
+use am_api::mock::MockServer;
+use am_api::primitive::TrackType;
+use am_api::resource::catalog::search::{CatalogSearch, CatalogSearchType};
+use am_api::resource::catalog::song::{Song, SongAttributes};
+use am_api::resource::catalog::station::{Station, StationGenre, StationGenreAttributes};
+use am_api::resource::genre::{Genre, GenreAttributes};
+use am_api::resource::history::History;
+use am_api::resource::library::playlist::LibraryPlaylist;
+use am_api::resource::library::search::{LibrarySearchRequestBuilder, LibrarySearchType};
+use am_api::resource::library::song::{
+    LibrarySong, LibrarySongAttributes, LibrarySongGetRequestBuilder,
+};
+use am_api::resource::library::LibraryAddResourceBuilder;
+use am_api::resource::personal_recommendation::PersonalRecommendation;
+use am_api::resource::rating::Rating;
+use am_api::resource::storefront::Storefront;
+use am_api::resource::{Resource, ResourceHeader, ResourceInfo};
+use am_api::retry::RetryPolicy;
+use futures::TryStreamExt;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+fn create_song(id: &str, name: &str) -> Song {
+    Song {
+        header: ResourceHeader {
+            id: id.to_string(),
+            href: format!("/v1/catalog/us/songs/{id}"),
+        },
+        attributes: Some(SongAttributes {
+            name: name.to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn header(id: &str, href: &str) -> ResourceHeader {
+    ResourceHeader {
+        id: id.to_string(),
+        href: href.to_string(),
+    }
+}
+
+fn resource_ids(resources: &[Resource]) -> Vec<String> {
+    resources
+        .iter()
+        .map(|e| e.get_header().id.clone())
+        .collect()
+}
+
+async fn create_server() -> Result<MockServer, Error> {
+    let server = MockServer::start().await?;
+    server.insert(create_song("1", "First"))?;
+    server.insert(create_song("2", "Second"))?;
+    server.insert(create_song("3", "Third"))?;
+    Ok(server)
+}
+
+#[tokio::test]
+async fn mock_serves_catalog() -> Result<(), Error> {
+    let server = create_server().await?;
+    let client = server.client()?;
+
+    let song = Song::get()
+        .one(&client, "1")
+        .await?
+        .expect("song fetch returned none");
+    assert_eq!(song.attributes.map(|e| e.name), Some(String::from("First")));
+
+    let songs = Song::get().many(&client, &["3", "1"], false).await?;
+    let ids = songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["3", "1"]);
+
+    let result = Song::get().one(&client, "404").await;
+    let Err(Error::NotFound(error)) = result else {
+        panic!("expected a not found error, got {result:?}");
+    };
+    let response = error.response.expect("error response was not parsed");
+    assert_eq!(response.errors[0].code, "40400");
+    assert_eq!(error.request_id, Some(response.errors[0].id.clone()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_resolves_isrc_filters() -> Result<(), Error> {
+    let server = create_server().await?;
+    let mut song = create_song("4", "Fourth");
+    if let Some(attributes) = &mut song.attributes {
+        attributes.isrc = Some(String::from("USRC17607839"));
+    }
+    server.insert(song)?;
+    let client = server.client()?;
+
+    let songs = Song::get()
+        .many(&client, &["USRC17607839", "GBAYE0000000"], true)
+        .await?;
+
+    assert_eq!(songs.data.len(), 1);
+    assert_eq!(songs.data[0].header.id, "4");
+    assert_eq!(songs.unresolved_ids(), vec!["GBAYE0000000"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_library_is_mutable_and_paginated() -> Result<(), Error> {
+    let server = create_server().await?;
+    let client = server.client()?;
+
+    let songs = Song::get().many(&client, &["1", "2", "3"], false).await?;
+    let mut builder = LibraryAddResourceBuilder::new();
+    for song in &songs.data {
+        builder = builder.add_resource(&song.clone().into())?;
+    }
+    builder.send(&client).await?;
+    assert_eq!(server.library("library-songs").len(), 3);
+
+    let library_songs = LibrarySongGetRequestBuilder::default()
+        .all(&client, 2, 0)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(library_songs.len(), 3);
+
+    let tracks = songs
+        .data
+        .iter()
+        .cloned()
+        .map(Resource::from)
+        .collect::<Vec<_>>();
+    let playlist = LibraryPlaylist::create("Mock")
+        .tracks(&[&tracks[0]])?
+        .create(&client)
+        .await?
+        .expect("playlist create returned none");
+    playlist
+        .add_tracks(&client, &[&tracks[1], &tracks[2]])
+        .await?;
+
+    let response = client
+        .get(&format!(
+            "/v1/me/library/playlists/{}/tracks",
+            playlist.header.id
+        ))
+        .query(&[("limit", "2")])
+        .send()
+        .await?;
+    let page = response.json::<serde_json::Value>()?;
+    assert_eq!(page["data"].as_array().map(Vec::len), Some(2));
+    assert_eq!(page["meta"]["total"], 3);
+    assert!(page["next"].is_string());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_ratings() -> Result<(), Error> {
+    let server = create_server().await?;
+    let client = server.client()?;
+
+    let song: Resource = create_song("1", "First").into();
+    let rating = Rating::add_rating()
+        .add_rating(&client, &song)
+        .await?
+        .expect("rating returned none");
+    assert_eq!(rating.header.id, "1");
+
+    Rating::remove_rating()
+        .remove_rating(&client, &song)
+        .await?;
+    assert!(matches!(
+        Rating::remove_rating().remove_rating(&client, &song).await,
+        Err(Error::NotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_programmed_responses() -> Result<(), Error> {
+    let server = create_server().await?;
+    let mut client = server.client()?;
+    client.set_retry_policy(RetryPolicy::none());
+
+    server.respond(
+        Method::GET,
+        "/v1/catalog/us/songs/1",
+        StatusCode::SERVICE_UNAVAILABLE,
+        json!({ "errors": [{ "status": "503", "code": "50300" }] }),
+    );
+
+    let response = client.get("/v1/catalog/us/songs/1").send().await?;
+    assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+
+    server.reset();
+    assert!(matches!(
+        Song::get().one(&client, "1").await,
+        Err(Error::NotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_lists_storefronts() -> Result<(), Error> {
+    let server = create_server().await?;
+    let client = server.client()?;
+
+    let storefronts = Storefront::get()
+        .all(&client, 10, 0)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let ids = storefronts
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["us"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_lists_top_chart_genres() -> Result<(), Error> {
+    let server = create_server().await?;
+    for (id, name) in [("14", "Pop"), ("20", "Alternative"), ("21", "Rock")] {
+        server.insert(Genre {
+            header: header(id, &format!("/v1/catalog/us/genres/{id}")),
+            attributes: Some(GenreAttributes {
+                name: name.to_string(),
+                ..Default::default()
+            }),
+        })?;
+    }
+    let client = server.client()?;
+
+    let genres = Genre::get()
+        .top_charts(&client, 2, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    let ids = genres
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["14", "20", "21"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_lists_station_genres() -> Result<(), Error> {
+    let server = create_server().await?;
+    for (id, name) in [("1", "Chill"), ("2", "Focus"), ("3", "Party")] {
+        server.insert(StationGenre {
+            header: header(id, &format!("/v1/catalog/us/station-genres/{id}")),
+            attributes: Some(StationGenreAttributes {
+                name: name.to_string(),
+            }),
+            ..Default::default()
+        })?;
+    }
+    let client = server.client()?;
+
+    let response = client
+        .get("/v1/catalog/us/station-genres")
+        .query(&[("limit", "2")])
+        .send()
+        .await?;
+    let page = response.json::<serde_json::Value>()?;
+    assert_eq!(
+        page["next"],
+        "/v1/catalog/us/station-genres?offset=2&limit=2"
+    );
+
+    let genres = StationGenre::get()
+        .all(&client, 2, 0)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(genres.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_catalog_search() -> Result<(), Error> {
+    let server = create_server().await?;
+    let client = server.client()?;
+
+    let results = CatalogSearch::search()
+        .search(
+            &client,
+            &[CatalogSearchType::Songs, CatalogSearchType::Albums],
+            "sec",
+        )
+        .await?;
+    let ids = results
+        .songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["2"]);
+    assert!(results.albums.data.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_library_search() -> Result<(), Error> {
+    let server = create_server().await?;
+    for (id, name) in [("i.1", "Morning Song"), ("i.2", "Evening Song")] {
+        server.insert_library(LibrarySong {
+            header: header(id, &format!("/v1/me/library/songs/{id}")),
+            attributes: Some(LibrarySongAttributes {
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })?;
+    }
+    let client = server.client()?;
+
+    let results = LibrarySearchRequestBuilder::default()
+        .search(&client, &[LibrarySearchType::LibrarySongs], "evening song")
+        .await?;
+    let ids = results
+        .library_songs
+        .data
+        .iter()
+        .map(|e| e.header.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["i.2"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_history() -> Result<(), Error> {
+    let server = create_server().await?;
+    let station = Station {
+        header: header("ra.1", "/v1/catalog/us/stations/ra.1"),
+        ..Default::default()
+    };
+    server.insert_played(station.clone())?;
+    server.insert_played(create_song("1", "First"))?;
+    server.insert_played(create_song("2", "Second"))?;
+    server.insert_played(station)?;
+    let client = server.client()?;
+
+    let tracks = History::get()
+        .recently_played_tracks(&client, &[TrackType::Song], 10, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(resource_ids(&tracks), vec!["2", "1"]);
+
+    let stations = History::get()
+        .recently_played_stations(&client, 10, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(resource_ids(&stations), vec!["ra.1"]);
+
+    let played = History::get()
+        .recently_played(&client, 10, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(resource_ids(&played), vec!["ra.1"]);
+
+    let heavy_rotation = History::get()
+        .heavy_rotation(&client, 10, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(resource_ids(&heavy_rotation), vec!["ra.1"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_recommendations() -> Result<(), Error> {
+    let server = create_server().await?;
+    for id in ["6-27s5hU6azhJY", "6-27s5hU6azhJZ"] {
+        server.insert(PersonalRecommendation {
+            header: header(id, &format!("/v1/me/recommendations/{id}")),
+            attributes: None,
+            relationships: Default::default(),
+        })?;
+    }
+    let client = server.client()?;
+
+    let recommendations = PersonalRecommendation::get()
+        .default_recommendations(&client, 1, 0)
+        .await
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(recommendations.len(), 2);
+
+    let recommendation = PersonalRecommendation::get()
+        .one(&client, "6-27s5hU6azhJZ")
+        .await?
+        .expect("recommendation fetch returned none");
+    assert_eq!(recommendation.header.id, "6-27s5hU6azhJZ");
+
+    Ok(())
+}
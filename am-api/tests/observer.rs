@@ -0,0 +1,90 @@
+use am_api::error::Error;
+use am_api::observer::{MetricsAggregator, Observer, RequestEvent, ResponseEvent};
+use am_api::resource::catalog::song::Song;
+use am_api::retry::RetryPolicy;
+use common::{create_fake_client, json_response, FakeTransport};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+/// Observer recording every event
+#[derive(Default)]
+struct RecordingObserver {
+    requests: Mutex<Vec<RequestEvent>>,
+    responses: Mutex<Vec<ResponseEvent>>,
+}
+
+impl Observer for RecordingObserver {
+    fn on_request(&self, event: &RequestEvent) {
+        self.requests.lock().unwrap().push(event.clone());
+    }
+
+    fn on_response(&self, event: &ResponseEvent) {
+        self.responses.lock().unwrap().push(event.clone());
+    }
+}
+
+fn song_transport() -> FakeTransport {
+    FakeTransport::new(|request| match request.url.contains("/songs/404") {
+        true => json_response(
+            404,
+            json!({ "errors": [{ "id": "1", "title": "Not Found", "detail": "Not Found", "status": "404", "code": "40400" }] }),
+        ),
+        false => json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] })),
+    })
+}
+
+#[tokio::test]
+async fn observer_receives_request_and_response_events() -> Result<(), Error> {
+    let observer = Arc::new(RecordingObserver::default());
+    let mut client = create_fake_client(song_transport());
+    client.add_observer(observer.clone());
+
+    Song::get().one(&client, "1").await?;
+
+    let requests = observer.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(requests[0].path, "/v1/catalog/us/songs/1");
+    assert_eq!(requests[0].endpoint, "/v1/catalog/{storefront}/songs/{id}");
+    assert_eq!(requests[0].attempt, 1);
+
+    let responses = observer.responses.lock().unwrap().clone();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].status, Some(StatusCode::OK));
+    assert!(responses[0].response_bytes > 0);
+    assert!(!responses[0].is_error());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn metrics_aggregate_by_endpoint() -> Result<(), Error> {
+    let metrics = Arc::new(MetricsAggregator::new());
+    let mut client = create_fake_client(song_transport());
+    client.set_retry_policy(RetryPolicy::none());
+    client.add_observer(metrics.clone());
+
+    Song::get().one(&client, "1").await?;
+    Song::get().one(&client, "2").await?;
+    assert!(Song::get().one(&client, "404").await.is_err());
+
+    let snapshot = metrics.snapshot();
+    let songs = &snapshot[&(
+        Method::GET,
+        "/v1/catalog/{storefront}/songs/{id}".to_string(),
+    )];
+    assert_eq!(songs.requests, 3);
+    assert_eq!(songs.errors, 1);
+    assert_eq!(songs.errors_by_code.get("40400"), Some(&1));
+    assert_eq!(songs.latency.count, 3);
+    assert_eq!(songs.latency.counts.iter().sum::<u64>(), 3);
+    assert_eq!(songs.response_bytes.count, 3);
+
+    metrics.reset();
+    assert!(metrics.snapshot().is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use am_api::error::Error;
+use am_api::request::paginated::{resume, Cursor, Page};
+use am_api::request::{try_resource_response, AttachContext};
+use am_api::resource::catalog::album::Album;
+use am_api::resource::library::song::{LibrarySong, LibrarySongGetRequestBuilder};
+use am_api::resource::storefront::Storefront;
+use am_api::resource::Resource;
+use futures::TryStreamExt;
+use serde_json::json;
+use std::pin::pin;
+
+mod common;
+
+fn page_ids<T>(page: &Page<T>, id: impl Fn(&T) -> &str) -> Vec<String> {
+    page.items.iter().map(|e| id(e).to_string()).collect()
+}
+
+#[tokio::test]
+async fn pages_follow_next() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let response = match request.query_value("offset") {
+            Some("0") => json!({
+                "data": [{ "id": "i.1", "type": "library-songs" }, { "id": "i.2", "type": "library-songs" }],
+                "next": "/v1/me/library/songs?offset=2",
+                "meta": { "total": 3 }
+            }),
+            Some("2") => json!({
+                "data": [{ "id": "i.3", "type": "library-songs" }],
+                "meta": { "total": 3 }
+            }),
+            _ => panic!("requested a page past the last one"),
+        };
+        common::json_response(200, response)
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let pages = LibrarySongGetRequestBuilder::default()
+        .all_pages(&client, 2, 0)
+        .try_collect::<Vec<Page<LibrarySong>>>()
+        .await?;
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(page_ids(&pages[0], |e| &e.header.id), ["i.1", "i.2"]);
+    assert_eq!(pages[0].total, Some(3));
+    assert_eq!(pages[0].next_offset, Some(2));
+    assert_eq!(page_ids(&pages[1], |e| &e.header.id), ["i.3"]);
+    assert_eq!(pages[1].next_offset, None);
+    assert_eq!(transport.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pages_stop_at_total() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let offset = request.query_value("offset").unwrap();
+        common::json_response(
+            200,
+            json!({ "data": [{ "id": offset, "type": "storefronts" }], "meta": { "total": 2 } }),
+        )
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let storefronts = Storefront::get()
+        .all(&client, 1, 0)
+        .map_ok(|e| e.header.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert_eq!(storefronts, ["0", "1"]);
+    assert_eq!(transport.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn response_exposes_meta_and_next() -> Result<(), Error> {
+    let response = common::json_response(
+        200,
+        json!({
+            "data": [],
+            "next": "/v1/me/library/songs?offset=25",
+            "meta": { "total": 100 }
+        }),
+    );
+
+    let response = try_resource_response::<LibrarySong>(response)?;
+
+    assert_eq!(
+        response.next.as_deref(),
+        Some("/v1/me/library/songs?offset=25")
+    );
+    assert_eq!(response.meta.total, Some(100));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resume_from_serialized_cursor() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let offset = request
+            .query_value("offset")
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        let next = (offset < 2).then(|| format!("/v1/me/library/songs?offset={}", offset + 1));
+        common::json_response(
+            200,
+            json!({
+                "data": [{ "id": format!("i.{offset}"), "type": "library-songs" }],
+                "next": next,
+                "meta": { "total": 3 }
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let mut pages = pin!(LibrarySongGetRequestBuilder::default().all_pages(&client, 1, 0));
+    let first = pages.try_next().await?.expect("no first page");
+    let cursor = first.cursor.expect("first page has no cursor");
+    assert_eq!(cursor.get_offset(), Some(1));
+
+    let cursor = serde_json::to_string(&cursor)?;
+    let cursor = serde_json::from_str::<Cursor>(&cursor)?;
+    let pages = resume::<LibrarySong>(&client, cursor)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(page_ids(&pages[0], |e| &e.header.id), ["i.1"]);
+    assert_eq!(page_ids(&pages[1], |e| &e.header.id), ["i.2"]);
+    assert!(pages[1].cursor.is_none());
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].query_value("limit"), Some("1"));
+    assert_eq!(requests[1].query_value("l"), Some("en-US"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resume_from_next_href() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let response = match request.query_value("page") {
+            Some("b") => json!({
+                "data": [{ "id": "2", "type": "songs" }],
+                "next": "/v1/catalog/us/albums/1/tracks?page=c"
+            }),
+            Some("c") => json!({ "data": [{ "id": "3", "type": "songs" }] }),
+            _ => panic!("unexpected page request"),
+        };
+        common::json_response(200, response)
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let mut album = serde_json::from_value::<Album>(json!({
+        "id": "1",
+        "type": "albums",
+        "relationships": {
+            "tracks": {
+                "href": "/v1/catalog/us/albums/1/tracks",
+                "next": "/v1/catalog/us/albums/1/tracks?page=b",
+                "data": [{ "id": "1", "type": "songs" }]
+            }
+        }
+    }))?;
+    album.attach_context(celes::Country::the_united_states_of_america(), "en-US");
+    let tracks = album.relationships.tracks.expect("album has no tracks");
+
+    let mut pages = pin!(tracks.pages(&client));
+    let first = pages.try_next().await?.expect("no first page");
+    assert_eq!(first.items.len(), 1);
+    assert!(transport.requests().is_empty());
+
+    let cursor = first.cursor.expect("first page has no cursor");
+    assert_eq!(cursor.get_href(), "/v1/catalog/us/albums/1/tracks?page=b");
+    assert_eq!(cursor.get_offset(), None);
+
+    let pages = resume::<Resource>(&client, cursor)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(
+        pages[0].cursor.as_ref().map(Cursor::get_href),
+        Some("/v1/catalog/us/albums/1/tracks?page=c")
+    );
+    assert!(pages[1].cursor.is_none());
+    assert_eq!(transport.requests().len(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use am_api::error::Error;
+use am_api::rate_limit::{RateLimit, RateLimiter};
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::song::LibrarySongGetRequestBuilder;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+mod common;
+
+fn create_transport() -> common::FakeTransport {
+    common::FakeTransport::new(|_| {
+        common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] }))
+    })
+}
+
+#[tokio::test]
+async fn rate_limit_shared_between_clones() -> Result<(), Error> {
+    let mut client = common::create_fake_client(create_transport());
+    client.set_rate_limiter(
+        RateLimiter::new().personalized(RateLimit::new(1, Duration::from_millis(100))),
+    );
+
+    let start = Instant::now();
+    let tasks = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                LibrarySongGetRequestBuilder::default()
+                    .one(&client, "i.1")
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        task.await.unwrap()?;
+    }
+
+    assert!(start.elapsed() >= Duration::from_millis(200));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limit_separate_budgets() -> Result<(), Error> {
+    let mut client = common::create_fake_client(create_transport());
+    client.set_rate_limiter(
+        RateLimiter::new()
+            .catalog(RateLimit::per_second(100))
+            .personalized(RateLimit::new(1, Duration::from_secs(60))),
+    );
+
+    LibrarySongGetRequestBuilder::default()
+        .one(&client, "i.1")
+        .await?;
+
+    let start = Instant::now();
+    for _ in 0..10 {
+        Song::get().one(&client, "1").await?;
+    }
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::LibraryAddResourceBuilder;
+use am_api::resource::storefront::Storefront;
+use am_api::retry::RetryPolicy;
+use am_api::ApiClient;
+use futures::StreamExt;
+use reqwest::Method;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+fn create_client(transport: common::FakeTransport) -> ApiClient {
+    let mut client = common::create_fake_client(transport);
+    client.set_retry_policy(RetryPolicy {
+        initial_backoff: Duration::ZERO,
+        jitter: false,
+        ..Default::default()
+    });
+    client
+}
+
+#[tokio::test]
+async fn retry_after_rate_limit() -> Result<(), Error> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let transport = common::FakeTransport::new({
+        let calls = calls.clone();
+        move |_| match calls.fetch_add(1, Ordering::SeqCst) {
+            0 => {
+                let mut response = common::json_response(429, json!({ "errors": [] }));
+                response
+                    .headers
+                    .insert("retry-after", "0".try_into().unwrap());
+                response
+            }
+            _ => common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] })),
+        }
+    });
+    let client = create_client(transport.clone());
+
+    let song = Song::get().one(&client, "1").await?;
+
+    assert!(song.is_some());
+    assert_eq!(transport.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_after_over_max_backoff_fails() {
+    let transport = common::FakeTransport::new(|_| {
+        let mut response = common::json_response(429, json!({ "errors": [] }));
+        response
+            .headers
+            .insert("retry-after", "3600".try_into().unwrap());
+        response
+    });
+    let client = create_client(transport.clone());
+
+    let result = Song::get().one(&client, "1").await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited { retry_after: Some(retry_after), .. })
+            if retry_after == Duration::from_secs(3600)
+    ));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[test]
+fn retry_backoff_saturates() {
+    let result = Ok(common::json_response(503, json!({ "errors": [] })));
+    let policy = RetryPolicy {
+        max_attempts: 10,
+        initial_backoff: Duration::from_secs(1),
+        max_backoff: Duration::MAX,
+        multiplier: f64::INFINITY,
+        jitter: false,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        policy.retry_delay(&Method::GET, 1, &result),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        policy.retry_delay(&Method::GET, 2, &result),
+        Some(Duration::MAX)
+    );
+
+    let policy = RetryPolicy {
+        initial_backoff: Duration::MAX,
+        multiplier: f64::NAN,
+        ..policy
+    };
+    assert_eq!(
+        policy.retry_delay(&Method::GET, 5, &result),
+        Some(Duration::MAX)
+    );
+}
+
+#[tokio::test]
+async fn retry_caps_attempts() {
+    let transport =
+        common::FakeTransport::new(|_| common::json_response(500, json!({ "errors": [] })));
+    let client = create_client(transport.clone());
+
+    let result = Song::get().one(&client, "1").await;
+
+    assert!(matches!(result, Err(Error::ServerError(_))));
+    assert_eq!(
+        transport.requests().len() as u32,
+        RetryPolicy::default().max_attempts
+    );
+}
+
+#[tokio::test]
+async fn retry_skips_non_idempotent() -> Result<(), Error> {
+    let transport =
+        common::FakeTransport::new(|_| common::json_response(503, json!({ "errors": [] })));
+    let client = create_client(transport.clone());
+
+    let result = LibraryAddResourceBuilder::new()
+        .add_resource(&Song::default().into())?
+        .send(&client)
+        .await;
+
+    assert!(matches!(result, Err(Error::ServerError(_))));
+    assert_eq!(transport.requests().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_resumes_pagination() -> Result<(), Error> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let transport = common::FakeTransport::new({
+        let calls = calls.clone();
+        move |request| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            let offset = request.query_value("offset").unwrap();
+            match (call, offset) {
+                (1, _) => common::json_response(503, json!({ "errors": [] })),
+                (_, "0") => {
+                    common::json_response(200, json!({ "data": [{ "id": "us" }, { "id": "gb" }] }))
+                }
+                (_, "2") => common::json_response(200, json!({ "data": [{ "id": "de" }] })),
+                _ => common::json_response(200, json!({ "data": [] })),
+            }
+        }
+    });
+    let client = create_client(transport.clone());
+
+    let storefronts = Storefront::get()
+        .all(&client, 2, 0)
+        .map(|e| e.map(|e| e.header.id))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(storefronts, ["us", "gb", "de"]);
+
+    let offsets = transport
+        .requests()
+        .iter()
+        .map(|e| e.query_value("offset").unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, ["0", "2", "2", "3"]);
+
+    Ok(())
+}
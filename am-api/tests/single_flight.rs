@@ -0,0 +1,148 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::resource::library::song::LibrarySongGetRequestBuilder;
+use am_api::token::StaticTokenProvider;
+use am_api::transport::{Transport, TransportRequest, TransportResponse};
+use am_api::ApiClient;
+use futures::future::BoxFuture;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+
+/// Transport answering after a delay so requests overlap
+#[derive(Clone, Default)]
+struct SlowTransport {
+    calls: Arc<AtomicUsize>,
+}
+
+impl Transport for SlowTransport {
+    fn send(&self, _: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(common::json_response(
+                200,
+                json!({ "data": [{ "id": "1", "type": "songs" }] }),
+            ))
+        })
+    }
+}
+
+/// Transport answering with a library song named after the media user token of the request
+#[derive(Clone, Default)]
+struct UserTransport {
+    calls: Arc<AtomicUsize>,
+}
+
+impl Transport for UserTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<TransportResponse, Error>> {
+        Box::pin(async move {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let token = request
+                .headers
+                .get("media-user-token")
+                .and_then(|e| e.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(common::json_response(
+                200,
+                json!({ "data": [{ "id": token, "type": "library-songs" }] }),
+            ))
+        })
+    }
+}
+
+fn create_client(transport: SlowTransport, coalesce_requests: bool) -> ApiClient {
+    ApiClient::builder()
+        .developer_token("developer-token")
+        .media_user_token("media-user-token")
+        .transport(transport)
+        .coalesce_requests(coalesce_requests)
+        .build()
+        .expect("failed to create api client")
+}
+
+async fn fetch_concurrently(client: &ApiClient, localizations: &[&str]) -> Result<(), Error> {
+    let tasks = localizations
+        .iter()
+        .map(|localization| {
+            let client = client.clone();
+            let localization = localization.to_string();
+            tokio::spawn(async move {
+                Song::get()
+                    .override_localization(&localization)
+                    .one(&client, "1")
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        let song = task.await.unwrap()?.expect("song fetch returned none");
+        assert_eq!(song.header.id, "1");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_flight_coalesces_identical_requests() -> Result<(), Error> {
+    let transport = SlowTransport::default();
+    let client = create_client(transport.clone(), true);
+
+    fetch_concurrently(&client, &["en-US"; 5]).await?;
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 1);
+
+    fetch_concurrently(&client, &["en-US", "en-GB", "en-US", "en-GB"]).await?;
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_flight_disabled_by_default() -> Result<(), Error> {
+    let transport = SlowTransport::default();
+    let client = create_client(transport.clone(), false);
+
+    fetch_concurrently(&client, &["en-US"; 5]).await?;
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn single_flight_skips_personalized_requests() -> Result<(), Error> {
+    let transport = UserTransport::default();
+    let first = ApiClient::builder()
+        .developer_token("developer-token")
+        .media_user_token("first-user")
+        .transport(transport.clone())
+        .coalesce_requests(true)
+        .build()?;
+    let mut second = first.clone();
+    second.set_token_provider(StaticTokenProvider::new(
+        "developer-token",
+        Some("second-user"),
+    )?);
+
+    let (a, b) = tokio::join!(
+        LibrarySongGetRequestBuilder::default().one(&first, "i.1"),
+        LibrarySongGetRequestBuilder::default().one(&second, "i.1")
+    );
+
+    assert_eq!(
+        a?.expect("song fetch returned none").header.id,
+        "first-user"
+    );
+    assert_eq!(
+        b?.expect("song fetch returned none").header.id,
+        "second-user"
+    );
+    assert_eq!(transport.calls.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
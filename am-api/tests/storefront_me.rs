@@ -0,0 +1,70 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use serde_json::json;
+
+mod common;
+
+fn create_transport() -> common::FakeTransport {
+    common::FakeTransport::new(
+        |request| match request.path("https://api.music.apple.com") {
+            "/v1/me/storefront" => common::json_response(
+                200,
+                json!({
+                    "data": [{
+                        "id": "gb",
+                        "type": "storefronts",
+                        "attributes": {
+                            "defaultLanguageTag": "en-GB",
+                            "explicitContentPolicy": "allowed",
+                            "name": "United Kingdom",
+                            "supportedLanguageTags": ["en-GB"]
+                        }
+                    }]
+                }),
+            ),
+            _ => common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] })),
+        },
+    )
+}
+
+#[tokio::test]
+async fn storefront_me() -> Result<(), Error> {
+    let transport = create_transport();
+    let mut client = common::create_fake_client(transport.clone());
+
+    let storefront = client.use_user_storefront().await?;
+
+    assert_eq!(storefront.header.id, "gb");
+    assert_eq!(
+        client.get_storefront_country(),
+        celes::Country::the_united_kingdom_of_great_britain_and_northern_ireland()
+    );
+    assert_eq!(client.get_localization(), "en-GB");
+
+    Song::get().one(&client, "1").await?;
+
+    let requests = transport.requests();
+    let request = requests.last().unwrap();
+    assert_eq!(
+        request.path(client.get_base_url()),
+        "/v1/catalog/gb/songs/1"
+    );
+    assert_eq!(request.query_value("l"), Some("en-GB"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn storefront_me_unknown() {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            200,
+            json!({ "data": [{ "id": "zz", "type": "storefronts" }] }),
+        )
+    });
+    let mut client = common::create_fake_client(transport);
+
+    let result = client.use_user_storefront().await;
+
+    assert!(matches!(result, Err(Error::UnknownStorefront(id)) if id == "zz"));
+}
@@ -0,0 +1,89 @@
+use am_api::error::Error;
+use am_api::resource::catalog::song::Song;
+use am_api::token::TokenProvider;
+use futures::future::BoxFuture;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+/// Token provider that hands out a new media user token on every refresh
+#[derive(Default)]
+struct RotatingTokenProvider {
+    generation: AtomicUsize,
+}
+
+impl TokenProvider for RotatingTokenProvider {
+    fn developer_token(&self) -> BoxFuture<'_, Result<String, Error>> {
+        Box::pin(async { Ok(String::from("developer-token")) })
+    }
+
+    fn media_user_token(&self) -> BoxFuture<'_, Result<Option<String>, Error>> {
+        Box::pin(async {
+            Ok(Some(format!(
+                "media-user-token-{}",
+                self.generation.load(Ordering::SeqCst)
+            )))
+        })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async {
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+    }
+}
+
+fn song_or_unauthorized(valid_token: &'static str) -> common::FakeTransport {
+    common::FakeTransport::new(move |request| {
+        match request.headers.get("media-user-token").unwrap() == valid_token {
+            true => common::json_response(200, json!({ "data": [{ "id": "1", "type": "songs" }] })),
+            false => common::json_response(401, json!({ "errors": [] })),
+        }
+    })
+}
+
+#[tokio::test]
+async fn refresh_on_unauthorized() -> Result<(), Error> {
+    let transport = song_or_unauthorized("media-user-token-1");
+    let mut client = common::create_fake_client(transport.clone());
+    let token_provider = Arc::new(RotatingTokenProvider::default());
+    client.set_token_provider(token_provider.clone());
+
+    let song = Song::get().one(&client, "1").await?;
+
+    assert!(song.is_some());
+    assert_eq!(transport.requests().len(), 2);
+    assert_eq!(token_provider.generation.load(Ordering::SeqCst), 1);
+
+    // Clones share the provider and keep using the refreshed token
+    Song::get().one(&client.clone(), "1").await?;
+    assert_eq!(transport.requests().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn refresh_only_once() {
+    let transport = song_or_unauthorized("never-valid");
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_token_provider(RotatingTokenProvider::default());
+
+    let result = Song::get().one(&client, "1").await;
+
+    assert!(matches!(result, Err(Error::Unauthorized(_))));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn refresh_skips_replay_with_unchanged_tokens() {
+    let transport = song_or_unauthorized("never-valid");
+    let client = common::create_fake_client(transport.clone());
+
+    let result = Song::get().one(&client, "1").await;
+
+    assert!(matches!(result, Err(Error::Unauthorized(_))));
+    assert_eq!(transport.requests().len(), 1);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "tracing")]
+
+use am_api::error::Error;
+use am_api::resource::storefront::Storefront;
+use am_api::retry::RetryPolicy;
+use futures::StreamExt;
+use serde_json::json;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing_subscriber::fmt::MakeWriter;
+
+mod common;
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Buffer {
+    type Writer = Buffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[tokio::test]
+async fn tracing_spans() -> Result<(), Error> {
+    let buffer = Buffer::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(buffer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let transport = common::FakeTransport::new({
+        let calls = calls.clone();
+        move |request| match (
+            calls.fetch_add(1, Ordering::SeqCst),
+            request.query_value("offset"),
+        ) {
+            (0, _) => common::json_response(503, json!({ "errors": [] })),
+            (_, Some("0")) => common::json_response(200, json!({ "data": [{ "id": "us" }] })),
+            _ => common::json_response(200, json!({ "data": [] })),
+        }
+    });
+    let mut client = common::create_fake_client(transport);
+    client.set_retry_policy(RetryPolicy {
+        initial_backoff: Duration::ZERO,
+        jitter: false,
+        ..Default::default()
+    });
+
+    let storefronts = Storefront::get()
+        .all(&client, 1, 0)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(storefronts.len(), 1);
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+    assert!(output.contains(
+        "am_api.paginate{endpoint=/v1/storefronts}:am_api.page{offset=0}:am_api.request{method=GET endpoint=/v1/storefronts attempt=1"
+    ));
+    assert!(output.contains("am_api.page{offset=1}:am_api.request"));
+    assert!(output.contains("retrying request attempt=1"));
+    assert!(output.contains("status=503"));
+    assert!(output.contains("[redacted]"));
+    assert!(!output.contains("developer-token"));
+    assert!(!output.contains("media-user-token\", \"media"));
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use am_api::error::Error;
+use am_api::resource::catalog::album::{Album, AlbumRelationshipType};
+use am_api::resource::storefront::Storefront;
+use am_api::resource::Resource;
+use futures::StreamExt;
+use serde_json::json;
+
+mod common;
+
+#[tokio::test]
+async fn fetch_album_fake() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            200,
+            json!({
+                "data": [{
+                    "id": "1676791755",
+                    "type": "albums",
+                    "href": "/v1/catalog/us/albums/1676791755",
+                    "attributes": { "name": "Unrequited Love - EP" }
+                }]
+            }),
+        )
+    });
+    let mut client = common::create_fake_client(transport.clone());
+    client.set_base_url("http://localhost:1234/");
+
+    let album = Album::get()
+        .one(&client, "1676791755")
+        .await?
+        .expect("album fetch returned none");
+
+    assert_eq!(album.attributes.unwrap().name, "Unrequited Love - EP");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].url,
+        "http://localhost:1234/v1/catalog/us/albums/1676791755"
+    );
+    assert_eq!(requests[0].query_value("l"), Some("en-US"));
+    assert_eq!(
+        requests[0].headers.get("authorization").unwrap(),
+        "Bearer developer-token"
+    );
+    assert_eq!(
+        requests[0].headers.get("media-user-token").unwrap(),
+        "media-user-token"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn fetch_album_fake_error() {
+    let transport = common::FakeTransport::new(|_| {
+        common::json_response(
+            404,
+            json!({
+                "errors": [{
+                    "id": "error-id",
+                    "title": "Resource Not Found",
+                    "detail": "Resource with requested id was not found",
+                    "status": "404",
+                    "code": "40400"
+                }]
+            }),
+        )
+    });
+    let client = common::create_fake_client(transport);
+
+    let result = Album::get().one(&client, "0").await;
+
+    match result {
+        Err(Error::NotFound(error)) => {
+            let response = error.response.expect("error response was not parsed");
+            assert_eq!(response.errors[0].code, "40400");
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn paginate_fake() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        let offset: usize = request.query_value("offset").unwrap().parse().unwrap();
+        let data = match offset {
+            0 => {
+                json!([{ "id": "us", "type": "storefronts" }, { "id": "gb", "type": "storefronts" }])
+            }
+            2 => json!([{ "id": "de", "type": "storefronts" }]),
+            _ => json!([]),
+        };
+        common::json_response(200, json!({ "data": data }))
+    });
+    let client = common::create_fake_client(transport.clone());
+
+    let storefronts = Storefront::get()
+        .all(&client, 2, 0)
+        .map(|e| e.map(|e| e.header.id))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(storefronts, ["us", "gb", "de"]);
+    assert_eq!(transport.requests().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn relationship_iter_fake() -> Result<(), Error> {
+    let transport = common::FakeTransport::new(|request| {
+        match request.path(am_api::transport::DEFAULT_BASE_URL) {
+            "/v1/catalog/us/albums/1" => common::json_response(
+                200,
+                json!({
+                    "data": [{
+                        "id": "1",
+                        "type": "albums",
+                        "relationships": {
+                            "tracks": {
+                                "href": "/v1/catalog/us/albums/1/tracks",
+                                "next": "/v1/catalog/us/albums/1/tracks?offset=1",
+                                "data": [{ "id": "10", "type": "songs" }]
+                            }
+                        }
+                    }]
+                }),
+            ),
+            "/v1/catalog/us/albums/1/tracks" => {
+                assert_eq!(request.query_value("offset"), Some("1"));
+                common::json_response(200, json!({ "data": [{ "id": "11", "type": "songs" }] }))
+            }
+            path => panic!("unexpected path {path}"),
+        }
+    });
+    let client = common::create_fake_client(transport);
+
+    let album = Album::get()
+        .include(AlbumRelationshipType::Tracks)
+        .one(&client, "1")
+        .await?
+        .expect("album fetch returned none");
+
+    let tracks = album
+        .relationships
+        .tracks
+        .expect("album fetch didn't return any track relationships")
+        .iter(&client)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ids = tracks
+        .iter()
+        .map(|e| match e {
+            Resource::Song { data } => data.header.id.as_str(),
+            _ => panic!("unexpected resource type"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["10", "11"]);
+
+    Ok(())
+}